@@ -1,6 +1,9 @@
 mod pages;
 
 fn main() -> iced::Result {
-    // run the home page easily
-    iced_kim::run(pages::home::Data::default(), iced::Font::MONOSPACE)
+    // run the home page, and exit the program when the home page is closed
+    iced_kim::application(pages::home::Data::default())
+        .default_font(iced::Font::MONOSPACE)
+        .exit_policy(iced_kim::ExitPolicy::MainWindowClosed)
+        .run()
 }
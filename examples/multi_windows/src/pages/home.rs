@@ -21,20 +21,18 @@ pub struct Data {}
 /// you may want use other functions, detail to see [IWindow]
 impl IWindow for Data {
     fn new_window(&self) -> iced_kim::Window {
-        iced_kim::Window {
-            title: "Main Window".to_string(),
-            data: Box::new(self.clone()),
+        iced_kim::Window::builder(self.clone())
+            .title("Main Window")
             // set the window config for the iced origin window
-            settings: iced::window::Settings {
+            .settings(iced::window::Settings {
                 min_size: Some(iced::Size::new(800.0, 400.0)),
                 size: iced::Size::new(1024.0, 768.0),
                 position: iced::window::Position::Centered,
                 resizable: true,
                 exit_on_close_request: false, // if you want to close the window immediately, you should set exit_on_close_request to false.
                 ..Default::default()
-            },
-            ..Default::default()
-        }
+            })
+            .build()
     }
 
     fn update(
@@ -55,11 +53,6 @@ impl IWindow for Data {
         }
     }
 
-    // when user close this window, you may want to exit the program immediately
-    fn on_close_request(&self, _id: iced::window::Id) -> Message {
-        Message::Exit
-    }
-
     fn view(
         &self,
         _window: &iced_kim::Window,
@@ -23,18 +23,16 @@ pub struct Data {
 /// you may want use other functions, detail to see [IWindow]
 impl IWindow for Data {
     fn new_window(&self) -> iced_kim::Window {
-        iced_kim::Window {
-            title: "Set Window".to_string(),
-            data: Box::new(self.clone()),
-            settings: iced::window::Settings {
+        iced_kim::Window::builder(self.clone())
+            .title("Set Window")
+            .settings(iced::window::Settings {
                 size: iced::Size::new(400.0, 300.0),
                 position: iced::window::Position::Centered,
                 resizable: false,
                 level: iced::window::Level::AlwaysOnTop,
                 ..Default::default()
-            },
-            ..Default::default()
-        }
+            })
+            .build()
     }
 
     fn update(
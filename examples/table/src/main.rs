@@ -73,6 +73,8 @@ impl Example {
             .width(400)
             .height(300)
             .row_height(40)
+            // * the table uses the default font of the program, you can change it at runtime
+            .font(iced::Font::with_name("微软雅黑"))
             // * you can selected row
             .on_row_selected(|row| Message::RowSelected(row).into())
             // * you can change the column width when press and move after hovering over the spliter
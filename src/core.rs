@@ -2,31 +2,250 @@
 // ! you need inherit [IWindow] and [IWindowMessage] for new window
 
 use iced::{window, Element, Theme};
-use std::{any::Any, fmt::Debug};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::widget::job::{JobInfo, JobMessage, Jobs};
 
 /// ! window base data
+/// * create it by `Window::builder(data)`
 #[derive(Debug, Clone)]
 pub struct Window {
     /// the window title
     pub title: String,
     /// the iced window settings
     pub settings: window::Settings,
-    /// parent window id, for transfer data
-    pub parent_id: Option<window::Id>,
-    /// child window id, current window will show modal when child id is some
-    pub child_id: Option<window::Id>,
     /// user data for current window
     pub data: Box<dyn IWindow>,
+    /// parent window id, for transfer data
+    pub(crate) parent_id: Option<window::Id>,
+    /// where to open the window
+    pub(crate) placement: WindowPlacement,
+    /// the logical position of the window, it is none before opened or unsupported
+    pub(crate) position: Option<iced::Point>,
+    /// the logical size of the window
+    pub(crate) size: iced::Size,
+    /// whether the window has the focus
+    pub(crate) focused: bool,
+    /// the focused window of the program, it is shared by every window
+    pub(crate) focused_window: Option<window::Id>,
+    /// the running jobs of the program, it is shared by every window
+    pub(crate) jobs: Jobs,
+    /// the theme of the program, it is shared by every window
+    pub(crate) theme: iced::Theme,
+    /// whether the window is minimized
+    pub(crate) minimized: bool,
+    /// whether the window is maximized
+    pub(crate) maximized: bool,
+    /// whether to flash the window when its blocked parent is activated
+    pub(crate) flash: bool,
+    /// the derived title when the title is set by `Message::TitleChanged`
+    /// * the override lasts until [IWindow::title] derives another title
+    pub(crate) overridden_title: Option<String>,
+    /// child window id, current window will show modal when child id is some
+    pub(crate) child_id: Option<window::Id>,
     /// the current window data type id, every window data is different
-    // TODO 本想做私有字段，但是到其他窗口使用 default的时候报错
-    pub data_type_id: std::any::TypeId,
+    pub(crate) data_type_id: std::any::TypeId,
     /// window close callback
     /// * it will create callback automatically when call show_dialog
-    pub window_closed_callback: Option<Box<Box<WindowCloseCallback>>>,
+    pub(crate) window_closed_callback: Option<WindowCloseCallback>,
+}
+
+impl Window {
+    /// create a window builder with the window data
+    /// # Example
+    /// ```ignore
+    /// Window::builder(self.clone()).title("Main Window").settings(settings).build()
+    /// ```
+    pub fn builder<T>(data: T) -> WindowBuilder
+    where
+        T: IWindow,
+    {
+        WindowBuilder::new(data)
+    }
+
+    /// parent window id
+    pub fn parent_id(&self) -> Option<window::Id> {
+        self.parent_id
+    }
+
+    /// child window id, current window shows modal when it is some
+    pub fn child_id(&self) -> Option<window::Id> {
+        self.child_id
+    }
+
+    /// the current window data type id
+    pub fn data_type_id(&self) -> std::any::TypeId {
+        self.data_type_id
+    }
+
+    /// the current logical position of the window
+    /// * it is none before the window opened or the platform doesn't support it
+    pub fn position(&self) -> Option<iced::Point> {
+        self.position
+    }
+
+    /// the current logical size of the window
+    pub fn size(&self) -> iced::Size {
+        self.size
+    }
+
+    /// whether the window has the focus
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// the focused window of the program, e.g. to check whether a dialog of the window has the focus
+    /// * it is none when the app is in the background
+    pub fn focused_window(&self) -> Option<window::Id> {
+        self.focused_window
+    }
+
+    /// the running jobs of the program, it can be called in the view
+    pub fn running_jobs(&self) -> Vec<JobInfo> {
+        self.jobs.list()
+    }
+
+    /// the theme of the program, e.g. to derive colors in the view
+    pub fn theme(&self) -> &iced::Theme {
+        &self.theme
+    }
+
+    /// whether the window is minimized
+    pub fn is_minimized(&self) -> bool {
+        self.minimized
+    }
+
+    /// whether the window is maximized
+    pub fn is_maximized(&self) -> bool {
+        self.maximized
+    }
+
+    /// whether the window has a close callback, see [Message::show_dialog]
+    pub fn has_close_callback(&self) -> bool {
+        self.window_closed_callback.is_some()
+    }
+}
+
+/// where to open a window
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum WindowPlacement {
+    /// use the position of the window settings
+    #[default]
+    Default,
+    /// centered on the parent window
+    CenteredOnParent,
+    /// cascaded from the parent window by the offset
+    Cascade(iced::Vector),
+    /// at the mouse cursor
+    AtCursor,
+}
+
+/// the window builder
+/// * `Window::builder(data).title(..).settings(..).icon(..).build()`
+#[derive(Debug, Clone)]
+pub struct WindowBuilder {
+    title: String,
+    settings: window::Settings,
+    icon: Option<window::Icon>,
+    placement: WindowPlacement,
+    flash: bool,
+    data: Box<dyn IWindow>,
+    data_type_id: std::any::TypeId,
+}
+
+impl WindowBuilder {
+    /// create a window builder with the window data
+    pub fn new<T>(data: T) -> Self
+    where
+        T: IWindow,
+    {
+        let data_type_id = data.type_id();
+        Self {
+            title: Default::default(),
+            settings: window::Settings {
+                icon: None,
+                ..Default::default()
+            },
+            icon: None,
+            placement: WindowPlacement::Default,
+            flash: true,
+            data: Box::new(data),
+            data_type_id,
+        }
+    }
+
+    /// set the window title
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// set the iced window settings
+    pub fn settings(mut self, settings: window::Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// set the window icon, the main window icon is used when it is none
+    pub fn icon(mut self, icon: window::Icon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// set where to open the window, it is relative to the parent window
+    /// * the position of the window settings is used when the parent position is unknown
+    pub fn placement(mut self, placement: WindowPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// whether to flash the window when it is a modal child and its blocked parent is activated
+    /// * default is true
+    pub fn flash(mut self, flash: bool) -> Self {
+        self.flash = flash;
+        self
+    }
+
+    /// build the window
+    pub fn build(self) -> Window {
+        let mut settings = self.settings;
+        if self.icon.is_some() {
+            settings.icon = self.icon;
+        }
+        Window {
+            title: self.title,
+            size: settings.size,
+            settings,
+            data: self.data,
+            placement: self.placement,
+            flash: self.flash,
+            position: None,
+            focused: false,
+            focused_window: None,
+            jobs: Jobs::default(),
+            theme: iced::Theme::default(),
+            minimized: false,
+            maximized: false,
+            parent_id: None,
+            overridden_title: None,
+            child_id: None,
+            data_type_id: self.data_type_id,
+            window_closed_callback: None,
+        }
+    }
 }
 
 /// ! a trait for window message, every window message will inherit it
-pub trait IWindowMessage: dyn_clone::DynClone + downcast_rs::Downcast {}
+/// * it is `Send`, so a message can be sent from other threads by [crate::ProgramHandle]
+pub trait IWindowMessage: dyn_clone::DynClone + downcast_rs::Downcast + Send {
+    /// the type name of the message, it is used by the diagnostics
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+}
 dyn_clone::clone_trait_object!(IWindowMessage);
 downcast_rs::impl_downcast!(IWindowMessage);
 
@@ -37,7 +256,8 @@ impl std::fmt::Debug for dyn IWindowMessage {
 }
 
 /// a trait for new window
-pub trait IWindow: dyn_clone::DynClone + downcast_rs::Downcast {
+/// * it is `Send`, because the window data is carried by [Message]
+pub trait IWindow: dyn_clone::DynClone + downcast_rs::Downcast + Send {
     /// use the window data to create a new window
     fn new_window(&self) -> Window;
 
@@ -48,6 +268,16 @@ pub trait IWindow: dyn_clone::DynClone + downcast_rs::Downcast {
     /// * `id` - the current window id
     fn view(&self, window: &Window, id: window::Id) -> Element<Message>;
 
+    /// the window title derived from the window data, it is consulted every frame
+    /// * an empty title - use the title of [Window]
+    /// * `Message::TitleChanged` overrides it until the derived title changes
+    /// # Arguments
+    /// * `id` - the current window id
+    fn title(&self, id: window::Id) -> String {
+        let _ = id;
+        String::new()
+    }
+
     /// this is where the window message is handled
     /// # Arguments
     /// * `message` - the global message, you need check the message type
@@ -70,6 +300,26 @@ pub trait IWindow: dyn_clone::DynClone + downcast_rs::Downcast {
         Message::None
     }
 
+    /// answer a request from another window, see [Message::request]
+    /// # Arguments
+    /// * `id` - the current window id
+    /// * `from` - the requester window id
+    /// * `message` - the request message, you need check the message type
+    /// # Return
+    /// * `None` - the request is not handled, the requester gets `RequestError::Unhandled`
+    /// * `Some(reply)` - the reply value routed back only to the requester
+    fn on_request(
+        &mut self,
+        id: &window::Id,
+        from: window::Id,
+        message: &std::boxed::Box<dyn IWindowMessage>,
+    ) -> Option<Box<dyn IWindowMessage>> {
+        let _ = id;
+        let _ = from;
+        let _ = message;
+        None
+    }
+
     /// the window opened callback
     /// # Arguments
     /// * `id` - the id of the window
@@ -106,6 +356,17 @@ pub trait IWindow: dyn_clone::DynClone + downcast_rs::Downcast {
         EventMessage::Close(id, DialogResult::None).into()
     }
 
+    /// the program is going to exit, every open window is asked
+    /// # Arguments
+    /// * `id` - current window id
+    /// # Return
+    /// * `ExitVote::Allow` - allow to exit, you can save your data here
+    /// * `ExitVote::Veto(message)` - refuse to exit, the message will be executed, e.g. show a message box
+    fn on_exit(&mut self, id: window::Id) -> ExitVote {
+        let _ = id;
+        ExitVote::Allow
+    }
+
     /// the window get focus
     /// # Arguments
     /// * `id` - the current window id
@@ -145,6 +406,34 @@ pub trait IWindow: dyn_clone::DynClone + downcast_rs::Downcast {
         let _ = height;
         Message::None
     }
+
+    /// a key pressed in the window and no widget captured it
+    /// # Arguments
+    /// * `id` - the current window id
+    /// * `key` - the pressed key
+    /// * `modifiers` - the keyboard modifiers
+    fn on_key_pressed(
+        &self,
+        id: window::Id,
+        key: iced::keyboard::Key,
+        modifiers: iced::keyboard::Modifiers,
+    ) -> Message {
+        let _ = id;
+        let _ = key;
+        let _ = modifiers;
+        Message::None
+    }
+
+    /// the app is launched again with the single instance mode, only the main window is called
+    /// * see `Application::single_instance()`
+    /// # Arguments
+    /// * `id` - the main window id
+    /// * `args` - the command-line arguments of the new launch, without the program name
+    fn on_instance_launched(&self, id: window::Id, args: Vec<String>) -> Message {
+        let _ = id;
+        let _ = args;
+        Message::None
+    }
 }
 dyn_clone::clone_trait_object!(IWindow);
 downcast_rs::impl_downcast!(IWindow);
@@ -156,25 +445,6 @@ impl std::fmt::Debug for dyn IWindow {
 
 /********************the window parameters*********************/
 
-impl Default for Window {
-    fn default() -> Self {
-        let data = EmptyWindowData::default();
-        let type_id = data.type_id();
-        Self {
-            title: Default::default(),
-            settings: window::Settings {
-                icon: None,
-                ..Default::default()
-            },
-            parent_id: None,
-            child_id: None,
-            data: Box::new(data),
-            data_type_id: type_id,
-            window_closed_callback: None,
-        }
-    }
-}
-
 /// window data
 /// * `WindowData::new(parent_id, data)`
 #[derive(Debug, Clone)]
@@ -182,8 +452,16 @@ impl Default for Window {
 pub struct WindowData {
     pub parent_id: Option<window::Id>,
     pub data: Box<dyn IWindow>,
-    pub data_type_id: std::any::TypeId,
-    pub callback: Option<*mut Box<WindowCloseCallback>>,
+    /// the window data type id, only one window of a type is opened
+    pub(crate) data_type_id: std::any::TypeId,
+    /// the close callback of a dialog, it is moved into the window when opened, see [Message::show_dialog]
+    pub(crate) callback: Option<WindowCloseCallback>,
+    /// a callback with the new window handle, see [Message::new_window_then]
+    pub(crate) opened_callback: Option<WindowOpenedCallback>,
+    /// block the parent window until the new window is closed, see [WindowData::modeless]
+    pub(crate) modal: bool,
+    /// a callback with the window data when the window is not opened, see [WindowData::on_refused]
+    pub(crate) refused_callback: Option<WindowRefusedCallback>,
 }
 
 impl WindowData {
@@ -197,33 +475,164 @@ impl WindowData {
             data: Box::new(data),
             data_type_id: type_id,
             callback: None,
+            opened_callback: None,
+            modal: true,
+            refused_callback: None,
         }
     }
+
+    /// create window data from a boxed window data, e.g. a panel floated out of a dock
+    pub fn from_box(parent_id: Option<window::Id>, data: Box<dyn IWindow>) -> Self {
+        let type_id = data.as_any().type_id();
+        WindowData {
+            parent_id,
+            data,
+            data_type_id: type_id,
+            callback: None,
+            opened_callback: None,
+            modal: true,
+            refused_callback: None,
+        }
+    }
+
+    /// the window data type id, only one window of a type is opened
+    pub fn data_type_id(&self) -> std::any::TypeId {
+        self.data_type_id
+    }
+
+    /// whether the window has a close callback, see [Message::show_dialog]
+    pub fn has_close_callback(&self) -> bool {
+        self.callback.is_some()
+    }
+
+    /// whether the window has an opened callback, see [Message::new_window_then]
+    pub fn has_opened_callback(&self) -> bool {
+        self.opened_callback.is_some()
+    }
+
+    /// open the window without blocking its parent
+    /// * the parent is only used to place the new window
+    pub fn modeless(mut self) -> Self {
+        self.modal = false;
+        self
+    }
+
+    /// get the window data back when the window is not opened
+    /// * e.g. a window of the same type is already open
+    pub fn on_refused(
+        mut self,
+        f: impl Fn(Box<dyn IWindow>) -> Message + Send + Sync + 'static,
+    ) -> Self {
+        self.refused_callback = Some(WindowRefusedCallback(Arc::new(f)));
+        self
+    }
 }
 
-/// ! an empty window data structure, use for the default window data
-#[derive(Debug, Clone)]
-struct EmptyWindowData;
+/// the callback with the refused window data, see [WindowData::on_refused]
+#[derive(Clone)]
+pub struct WindowRefusedCallback(Arc<dyn Fn(Box<dyn IWindow>) -> Message + Send + Sync>);
+
+impl WindowRefusedCallback {
+    /// create the message with the window data which was not opened
+    pub(crate) fn call(&self, data: Box<dyn IWindow>) -> Message {
+        (self.0)(data)
+    }
+}
+
+impl std::fmt::Debug for WindowRefusedCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WindowRefusedCallback")
+    }
+}
+
+/// the callback with the new window handle, see [Message::new_window_then]
+#[derive(Clone)]
+pub struct WindowOpenedCallback(Arc<dyn Fn(window::Id, Arc<AtomicBool>) -> Message + Send + Sync>);
 
-impl Default for EmptyWindowData {
-    fn default() -> Self {
-        Self {}
+impl WindowOpenedCallback {
+    /// create the message with the new window id and its alive flag
+    pub(crate) fn call(&self, id: window::Id, alive: Arc<AtomicBool>) -> Message {
+        (self.0)(id, alive)
     }
 }
 
-impl IWindow for EmptyWindowData {
-    fn new_window(&self) -> Window {
-        todo!()
+impl std::fmt::Debug for WindowOpenedCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WindowOpenedCallback")
     }
+}
 
-    fn view(&self, _window: &Window, _id: window::Id) -> Element<Message> {
-        todo!()
+/// a typed handle of an opened window
+/// * get it by [Message::new_window_then]
+pub struct WindowHandle<T> {
+    id: window::Id,
+    alive: Arc<AtomicBool>,
+    _data: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for WindowHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            alive: self.alive.clone(),
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for WindowHandle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WindowHandle")
+            .field("id", &self.id)
+            .field("alive", &self.alive.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl<T> PartialEq for WindowHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> WindowHandle<T>
+where
+    T: IWindow,
+{
+    /// the window id
+    pub fn id(&self) -> window::Id {
+        self.id
+    }
+
+    /// whether the window is still open
+    pub fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Relaxed)
+    }
+
+    /// a message to send the window message only to this window
+    pub fn send<M>(&self, msg: M) -> Message
+    where
+        M: IWindowMessage,
+    {
+        Message::send_to(self.id, msg)
+    }
+
+    /// a message to close this window
+    /// * `dialog_result` - the dialog result of the window
+    pub fn close(&self, dialog_result: DialogResult) -> Message {
+        EventMessage::Close(self.id, dialog_result).into()
+    }
+
+    /// a message to bring this window to the front and focus it
+    pub fn focus(&self) -> Message {
+        Message::Focus(self.id)
     }
 }
 
 /// ! window close callback
 pub type WindowCloseCallback = fn(data: WindowCloseCallbackData) -> Message;
 /// the window close callback data
+#[allow(clippy::borrowed_box)]
 pub struct WindowCloseCallbackData<'a> {
     /// the window id of close window
     pub id: window::Id,
@@ -233,6 +642,9 @@ pub struct WindowCloseCallbackData<'a> {
     /// * you can call window.clone().downcast::<Data>()
     /// * or call get_window_data::<T>()
     pub window: &'a Box<dyn IWindow>,
+    /// the close window data, e.g. the data collected by the dialog
+    /// * you can call get_dialog_data::<T>()
+    pub dialog: &'a Box<dyn IWindow>,
 }
 
 impl<'a> WindowCloseCallbackData<'a> {
@@ -251,6 +663,22 @@ impl<'a> WindowCloseCallbackData<'a> {
             Err(_) => None,
         }
     }
+
+    /// get the close window data
+    /// # Example
+    /// ```ignore
+    /// r.get_dialog_data::<Your Dialog Data>()
+    /// ```
+    pub fn get_dialog_data<T>(&self) -> Option<T>
+    where
+        T: IWindow,
+    {
+        let r = self.dialog.clone().downcast::<T>();
+        match r {
+            Ok(data) => Some(*data),
+            Err(_) => None,
+        }
+    }
 }
 
 /********************Window Message*********************/
@@ -264,7 +692,7 @@ pub enum Message {
     Theme(Theme),
     /// change window scale
     Scale(f64),
-    /// change the window title
+    /// change the window title, it overrides [IWindow::title]
     TitleChanged(window::Id, String),
     /// the window event message by iced
     EventMessage(EventMessage),
@@ -288,20 +716,29 @@ pub enum Message {
     ///
     /// ```
     WindowMessage(WindowMessage),
+    /// `user define` a window message only for the window
+    /// * `Message::send_to()` - you can use this
+    SendTo(window::Id, WindowMessage),
+    /// bring the window to the front and focus it
+    Focus(window::Id),
     /// `user define` a new window message
     /// * `Message::show_dialog()` - you can use this
     /// * `Message::new_window()`- or use this
     NewWindow(WindowData),
+    /// `user define` a request to another window, the reply is routed back only to the requester
+    /// * `Message::request()` - you can use this
+    Request(RequestData),
     /// multiple message
     /// * `Message::events()` - you can use this
     /// * `Message::window_messages()` - or use this
     MultMessage(Vec<Message>),
-    /// exit the program
+    /// a background job message
+    /// * `Job::thread()` or `Job::future()` - start a job by this
+    Job(JobMessage),
+    /// exit the program, every open window is asked by [IWindow::on_exit] before exiting
     Exit,
 }
 
-unsafe impl std::marker::Send for Message {}
-
 // Message functions
 impl Message {
     /// create a single window event message
@@ -351,6 +788,42 @@ impl Message {
         Message::NewWindow(WindowData::new(Some(id), data))
     }
 
+    /// a message to create a new window and get its typed handle
+    /// * the handle is given to the existing window when the window is already open
+    /// # Arguments
+    /// * `id` - current window id, this will be a parent id for the new window
+    /// * `data` - data for new window
+    /// * `then` - create a message with the new window handle
+    /// # Example
+    /// ```ignore
+    /// Message::new_window_then(*id, set::Data::default(), |handle| Msg::SetOpened(handle).into())
+    /// ```
+    pub fn new_window_then<T>(id: window::Id, data: T, then: fn(WindowHandle<T>) -> Message) -> Self
+    where
+        T: IWindow,
+    {
+        let mut window_data = WindowData::new(Some(id), data);
+        window_data.opened_callback = Some(WindowOpenedCallback(Arc::new(move |id, alive| {
+            then(WindowHandle {
+                id,
+                alive,
+                _data: PhantomData,
+            })
+        })));
+        Message::NewWindow(window_data)
+    }
+
+    /// create a window message only for the window
+    /// # Arguments
+    /// * `id` - the window to receive the message
+    /// * `msg` - the window message
+    pub fn send_to<T>(id: window::Id, msg: T) -> Self
+    where
+        T: IWindowMessage,
+    {
+        Message::SendTo(id, WindowMessage::new(msg))
+    }
+
     /// a message to create a new window with a callback when the window closed
     /// # Arguments
     /// * `id` - current window id, this will be a parent id for the new window
@@ -364,16 +837,107 @@ impl Message {
     where
         T: IWindow,
     {
-        let a = Box::new(Box::new(window_close_callback));
-        let p = Box::into_raw(a);
         let type_id = data.type_id();
         Message::NewWindow(WindowData {
             parent_id: Some(id),
             data: Box::new(data),
             data_type_id: type_id,
-            callback: Some(p),
+            callback: Some(window_close_callback),
+            opened_callback: None,
+            modal: true,
+            refused_callback: None,
+        })
+    }
+
+    /// a message to request a reply from another window
+    /// * the requester is filled by the framework when the message is returned from the window update or hooks
+    /// * use [Message::request_from] in the view
+    /// * a request without requester is dropped, it is never answered
+    /// # Arguments
+    /// * `target` - the window to answer the request, see [IWindow::on_request]
+    /// * `msg` - the request message
+    /// * `on_reply` - convert the reply to a message of the requester, it is only sent to the requester
+    pub fn request<T, R, M>(
+        target: window::Id,
+        msg: T,
+        on_reply: fn(Result<R, RequestError>) -> M,
+    ) -> Self
+    where
+        T: IWindowMessage,
+        R: IWindowMessage + Clone,
+        M: IWindowMessage,
+    {
+        let on_reply: ReplyCallback = Arc::new(move |reply| {
+            let reply = reply.and_then(|reply| {
+                reply
+                    .downcast::<R>()
+                    .map(|reply| *reply)
+                    .map_err(|_| RequestError::Mismatched)
+            });
+            Box::new(on_reply(reply))
+        });
+        Message::Request(RequestData {
+            from: None,
+            target,
+            msg: Box::new(msg),
+            on_reply,
         })
     }
+
+    /// a message to request a reply from another window with the requester id
+    /// # Arguments
+    /// * `id` - the requester window id
+    /// * `target` - the window to answer the request, see [IWindow::on_request]
+    /// * `msg` - the request message
+    /// * `on_reply` - convert the reply to a message of the requester, it is only sent to the requester
+    pub fn request_from<T, R, M>(
+        id: window::Id,
+        target: window::Id,
+        msg: T,
+        on_reply: fn(Result<R, RequestError>) -> M,
+    ) -> Self
+    where
+        T: IWindowMessage,
+        R: IWindowMessage + Clone,
+        M: IWindowMessage,
+    {
+        Message::request(target, msg, on_reply).with_origin(id)
+    }
+
+    /// fill the requester of the requests without requester
+    pub(crate) fn with_origin(self, id: window::Id) -> Self {
+        match self {
+            Message::Request(mut request) => {
+                request.from.get_or_insert(id);
+                Message::Request(request)
+            }
+            Message::MultMessage(msgs) => {
+                Message::MultMessage(msgs.into_iter().map(|msg| msg.with_origin(id)).collect())
+            }
+            msg => msg,
+        }
+    }
+
+    /// the readable type of the message, it is used by the diagnostics
+    pub(crate) fn type_name(&self) -> String {
+        match self {
+            Message::None => "None".into(),
+            Message::Theme(_) => "Theme".into(),
+            Message::Scale(_) => "Scale".into(),
+            Message::TitleChanged(..) => "TitleChanged".into(),
+            Message::EventMessage(msg) => format!("EventMessage({:?})", msg),
+            Message::WindowMessage(msg) => {
+                format!("WindowMessage({})", msg.msg.as_ref().type_name())
+            }
+            Message::SendTo(_, msg) => format!("SendTo({})", msg.msg.as_ref().type_name()),
+            Message::Focus(_) => "Focus".into(),
+            Message::NewWindow(_) => "NewWindow".into(),
+            Message::Request(request) => format!("Request({})", request.msg.as_ref().type_name()),
+            Message::MultMessage(_) => "MultMessage".into(),
+            Message::Job(msg) => format!("Job({:?})", msg),
+            Message::Exit => "Exit".into(),
+        }
+    }
 }
 
 impl From<Vec<Message>> for Message {
@@ -428,6 +992,36 @@ pub enum EventMessage {
     CloseRequest(window::Id),
     Focused(window::Id),
     Unfocused(window::Id),
+    /// deliver the coalesced moved and resized events which are ready
+    /// * it is sent by every frame or the settle timer while some events are pending
+    Flush(std::time::Instant),
+    /// the window minimized and maximized state is queried
+    State {
+        id: window::Id,
+        minimized: bool,
+        maximized: bool,
+    },
+    /// the cursor is moved in the window, it is used by `WindowPlacement::AtCursor`
+    CursorMoved {
+        id: window::Id,
+        position: iced::Point,
+    },
+    KeyPressed {
+        id: window::Id,
+        key: iced::keyboard::Key,
+        modifiers: iced::keyboard::Modifiers,
+    },
+    /// the app is launched again with the arguments, see [IWindow::on_instance_launched]
+    InstanceLaunched(Vec<String>),
+}
+
+/// the reply of a window to the exit, see [IWindow::on_exit]
+#[derive(Debug, Clone)]
+pub enum ExitVote {
+    /// allow to exit
+    Allow,
+    /// refuse to exit and execute the message
+    Veto(Message),
 }
 
 /// dialog result like winform
@@ -470,4 +1064,48 @@ impl WindowMessage {
             msg_type_id,
         }
     }
+
+    /// create a new window message from a boxed message
+    pub fn from_box(msg: Box<dyn IWindowMessage>) -> Self {
+        let msg_type_id = msg.as_any().type_id();
+        WindowMessage { msg, msg_type_id }
+    }
+}
+
+/// the reply callback of a request, see [Message::request]
+pub type ReplyCallback = Arc<
+    dyn Fn(Result<Box<dyn IWindowMessage>, RequestError>) -> Box<dyn IWindowMessage> + Send + Sync,
+>;
+
+/// the request to another window
+#[derive(Clone)]
+pub struct RequestData {
+    /// the requester window id
+    pub from: Option<window::Id>,
+    /// the window to answer the request
+    pub target: window::Id,
+    /// the request message
+    pub msg: Box<dyn IWindowMessage>,
+    /// convert the reply to a message of the requester
+    pub on_reply: ReplyCallback,
+}
+
+impl std::fmt::Debug for RequestData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestData")
+            .field("from", &self.from)
+            .field("target", &self.target)
+            .finish()
+    }
+}
+
+/// the error of a request
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum RequestError {
+    /// the target window is closed
+    Closed,
+    /// the target window doesn't answer the request
+    Unhandled,
+    /// the reply is not the expected type
+    Mismatched,
 }
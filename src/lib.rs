@@ -3,10 +3,22 @@ mod widget;
 
 #[cfg(feature = "multi-windows")]
 #[doc(no_inline)]
-pub use crate::widget::multi_windows::{run, Program};
+pub use crate::widget::job::{Job, JobContext, JobId, JobInfo, JobMessage};
 #[cfg(feature = "multi-windows")]
 #[doc(no_inline)]
-pub use core::{DialogResult, EventMessage, IWindow, IWindowMessage, Message, Window};
+pub use crate::widget::multi_windows::{
+    application, run, Application, EventCoalescing, ExitPolicy, MessageLimit, Program,
+    ProgramExtension,
+};
+#[cfg(feature = "multi-windows")]
+#[doc(no_inline)]
+pub use crate::widget::program_handle::ProgramHandle;
+#[cfg(feature = "multi-windows")]
+#[doc(no_inline)]
+pub use core::{
+    DialogResult, EventMessage, ExitVote, IWindow, IWindowMessage, Message, RequestError, Window,
+    WindowBuilder, WindowHandle, WindowPlacement,
+};
 #[cfg(feature = "multi-windows")]
 pub use iced_kim_macro::Message;
 
@@ -15,7 +27,54 @@ pub use iced_kim_macro::Message;
 pub use crate::widget::message_box::{message_box, MessageBox};
 #[cfg(feature = "message-box")]
 #[doc(no_inline)]
-pub use crate::widget::{message_box_button, MessageBoxButton};
+pub use crate::widget::{message_box_button, MessageBoxButton, MessageBoxIcon};
+
+#[cfg(feature = "dock")]
+#[doc(no_inline)]
+pub use crate::widget::dock::{dock, Dock, DockAxis, DockLayout, DockMessage, DockPanel};
+
+#[cfg(feature = "navigator")]
+#[doc(no_inline)]
+pub use crate::widget::navigator::{navigator, IPage, Navigator, NavigatorMessage};
+
+#[cfg(feature = "wizard")]
+#[doc(no_inline)]
+pub use crate::widget::wizard::{wizard, IWizardStep, Wizard, WizardData, WizardMessage};
+
+#[cfg(feature = "color-dialog")]
+#[doc(no_inline)]
+pub use crate::widget::color_dialog::{
+    color_dialog, ColorDialog, ColorDialogData, ColorDialogMessage, Hsv,
+};
+
+#[cfg(feature = "file-dialog")]
+#[doc(no_inline)]
+pub use crate::widget::file_dialog::{
+    FileDialog, FileDialogData, FileDialogMessage, FileDialogMode, FileFilter, FolderBrowserDialog,
+    OpenFileDialog, SaveFileDialog,
+};
+
+#[cfg(feature = "font-dialog")]
+#[doc(no_inline)]
+pub use crate::widget::font_dialog::{
+    font_dialog, font_families, FontDialog, FontDialogData, FontDialogMessage,
+};
+
+#[cfg(feature = "input-box")]
+#[doc(no_inline)]
+pub use crate::widget::input_box::{
+    input_box, InputBox, InputBoxData, InputBoxMessage, InputKind, InputValidator,
+};
+
+#[cfg(feature = "progress-dialog")]
+#[doc(no_inline)]
+pub use crate::widget::progress_dialog::{
+    progress_dialog, ProgressDialog, ProgressDialogData, ProgressMessage,
+};
+
+#[cfg(feature = "theme")]
+#[doc(no_inline)]
+pub use crate::widget::theme::{load_theme, watch_theme, ThemeError, ThemePalette};
 
 #[cfg(feature = "table")]
 #[doc(no_inline)]
@@ -0,0 +1,776 @@
+// ! a color picker dialog like winform ColorDialog
+// ! get the chosen color in the close callback by `get_dialog_data::<ColorDialogData>()`
+
+use iced::advanced::renderer::{self, Quad};
+use iced::advanced::widget::tree::{self, Tree};
+use iced::advanced::{layout, Clipboard, Layout, Shell, Widget};
+use iced::event::{self, Event};
+use iced::gradient::Linear;
+use iced::widget::{button, column, container, row, slider, text, text_input, Space};
+use iced::window::Level;
+use iced::{mouse, window, Alignment, Element, Length, Rectangle, Size, Theme};
+use iced::{Background, Border, Color, Degrees, Gradient};
+
+use super::{message_box_button, MessageBoxButton};
+use crate::core::{
+    DialogResult, EventMessage, IWindow, IWindowMessage, Message, Window, WindowCloseCallback,
+    WindowPlacement,
+};
+
+/// the max count of the recent colors
+const RECENT_LIMIT: usize = 12;
+/// the size of a swatch
+const SWATCH: f32 = 22.0;
+
+/// add the chosen color to the recent colors, the latest first
+fn push_recent(colors: &mut Vec<Color>, color: Color) {
+    colors.retain(|recent| *recent != color);
+    colors.insert(0, color);
+    colors.truncate(RECENT_LIMIT);
+}
+
+/// create a new color dialog with a new window
+/// * `title` - the title of the color dialog
+pub fn color_dialog(title: impl Into<String>) -> ColorDialog {
+    ColorDialog::new(title)
+}
+
+/// a color in the HSV model
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Hsv {
+    /// the hue in `0.0..360.0`
+    pub hue: f32,
+    /// the saturation in `0.0..=1.0`
+    pub saturation: f32,
+    /// the value in `0.0..=1.0`
+    pub value: f32,
+}
+
+impl Hsv {
+    /// convert the rgb color, the alpha is ignored
+    pub fn from_color(color: Color) -> Self {
+        let max = color.r.max(color.g).max(color.b);
+        let min = color.r.min(color.g).min(color.b);
+        let delta = max - min;
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == color.r {
+            60.0 * ((color.g - color.b) / delta).rem_euclid(6.0)
+        } else if max == color.g {
+            60.0 * ((color.b - color.r) / delta + 2.0)
+        } else {
+            60.0 * ((color.r - color.g) / delta + 4.0)
+        };
+        Self {
+            hue,
+            saturation: if max == 0.0 { 0.0 } else { delta / max },
+            value: max,
+        }
+    }
+
+    /// convert to the rgb color with the alpha
+    pub fn to_color(self, alpha: f32) -> Color {
+        let chroma = self.value * self.saturation;
+        let sector = self.hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (r, g, b) = match sector as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = self.value - chroma;
+        Color::from_rgba(r + m, g + m, b + m, alpha)
+    }
+}
+
+/// the hex text of the color, the alpha is only shown when it's not opaque
+pub fn to_hex(color: Color) -> String {
+    let [r, g, b, a] = color.into_rgba8();
+    match a {
+        255 => format!("#{:02X}{:02X}{:02X}", r, g, b),
+        _ => format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a),
+    }
+}
+
+/// the color dialog message, the window id is the color dialog window
+#[derive(Debug, Clone)]
+pub enum ColorDialogMessage {
+    /// the saturation and the value picked in the HSV square
+    SaturationValue(window::Id, f32, f32),
+    Hue(window::Id, f32),
+    Alpha(window::Id, f32),
+    /// the red, green or blue input by the index
+    Channel(window::Id, usize, String),
+    Hex(window::Id, String),
+    /// a swatch or a recent color is picked
+    Picked(window::Id, Color),
+    Accept(window::Id),
+}
+
+impl IWindowMessage for ColorDialogMessage {}
+
+/// ! the color dialog window data
+#[derive(Debug, Clone)]
+pub struct ColorDialogData {
+    pub title: String,
+    /// ! the chosen color when the dialog is closed with `DialogResult::Ok`
+    pub color: Color,
+    /// whether the alpha can be edited
+    pub alpha: bool,
+    /// the swatches, `None` is the palette of the program theme
+    pub swatches: Option<Vec<Color>>,
+    /// the colors chosen recently, the latest first
+    /// * the chosen color is added when the dialog is closed with `DialogResult::Ok`,
+    ///   keep it for the next dialog by [ColorDialog::recent_colors]
+    pub recent: Vec<Color>,
+    pub ok: MessageBoxButton,
+    pub cancel: MessageBoxButton,
+    pub window_settings: iced::window::Settings,
+    /// the color when the dialog is shown
+    initial: Color,
+    /// ! the source of the color, the hue is kept when the color is gray
+    hsv: Hsv,
+    rgb_input: [String; 3],
+    hex_input: String,
+}
+
+impl Default for ColorDialogData {
+    fn default() -> Self {
+        Self {
+            title: "颜色".into(),
+            color: Color::WHITE,
+            alpha: true,
+            swatches: None,
+            recent: Vec::new(),
+            ok: message_box_button("确定").dialog_result(DialogResult::Ok),
+            cancel: message_box_button("取消").dialog_result(DialogResult::Cancel),
+            window_settings: iced::window::Settings {
+                min_size: Some(iced::Size::new(480.0, 420.0)),
+                size: iced::Size::new(480.0, 420.0),
+                position: iced::window::Position::Centered,
+                level: Level::AlwaysOnTop,
+                resizable: false,
+                icon: None,
+                ..Default::default()
+            },
+            initial: Color::WHITE,
+            hsv: Hsv::from_color(Color::WHITE),
+            rgb_input: Default::default(),
+            hex_input: String::new(),
+        }
+    }
+}
+
+/// the swatches of the theme palette
+fn theme_swatches(theme: &Theme) -> Vec<Color> {
+    let palette = theme.extended_palette();
+    vec![
+        palette.primary.weak.color,
+        palette.primary.base.color,
+        palette.primary.strong.color,
+        palette.secondary.weak.color,
+        palette.secondary.base.color,
+        palette.secondary.strong.color,
+        palette.success.weak.color,
+        palette.success.base.color,
+        palette.success.strong.color,
+        palette.danger.weak.color,
+        palette.danger.base.color,
+        palette.danger.strong.color,
+        palette.background.base.color,
+        palette.background.weak.color,
+        palette.background.strong.color,
+        palette.background.base.text,
+    ]
+}
+
+impl ColorDialogData {
+    /// set the color from the rgb, keep the input being edited
+    fn set_color(&mut self, color: Color) {
+        self.hsv = Hsv::from_color(color);
+        self.color = color;
+    }
+
+    /// update the color by the hsv and the alpha
+    fn apply_hsv(&mut self) {
+        self.color = self.hsv.to_color(self.color.a);
+    }
+
+    /// show the color in the rgb and hex inputs
+    fn refresh_inputs(&mut self) {
+        self.refresh_rgb();
+        self.refresh_hex();
+    }
+
+    fn refresh_hex(&mut self) {
+        self.hex_input = to_hex(self.color);
+    }
+
+    fn refresh_rgb(&mut self) {
+        let [r, g, b, _] = self.color.into_rgba8();
+        self.rgb_input = [r, g, b].map(|channel| channel.to_string());
+    }
+
+    /// the rgb or hex input
+    fn input<'a>(
+        &'a self,
+        label: &'a str,
+        value: &'a str,
+        on_input: impl Fn(String) -> Message + 'a,
+    ) -> Element<'a, Message> {
+        row![
+            text(label).width(40),
+            text_input("", value).on_input(on_input)
+        ]
+        .spacing(5)
+        .align_y(Alignment::Center)
+        .into()
+    }
+}
+
+/// a square of the color
+fn swatch<'a>(color: Color, on_press: Option<Message>) -> Element<'a, Message> {
+    button(Space::new(SWATCH, SWATCH))
+        .padding(0)
+        .on_press_maybe(on_press)
+        .style(move |theme: &Theme, status| button::Style {
+            background: Some(color.into()),
+            border: Border {
+                color: match status {
+                    button::Status::Hovered => theme.extended_palette().primary.strong.color,
+                    _ => theme.extended_palette().background.strong.color,
+                },
+                width: 1.0,
+                radius: 3.0.into(),
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+/// a row of the swatches
+fn swatches<'a>(id: window::Id, colors: &[Color]) -> Element<'a, Message> {
+    row(colors
+        .iter()
+        .map(|color| swatch(*color, Some(ColorDialogMessage::Picked(id, *color).into()))))
+    .spacing(4)
+    .wrap()
+    .into()
+}
+
+impl IWindow for ColorDialogData {
+    fn new_window(&self) -> Window {
+        Window::builder(self.to_owned())
+            .title(self.title.clone())
+            .settings(self.window_settings.clone())
+            .placement(WindowPlacement::CenteredOnParent)
+            .build()
+    }
+
+    fn update(
+        &mut self,
+        id: &window::Id,
+        message: &std::boxed::Box<dyn IWindowMessage>,
+    ) -> Message {
+        let Some(msg) = message.downcast_ref::<ColorDialogMessage>() else {
+            return Message::None;
+        };
+        match msg.clone() {
+            ColorDialogMessage::SaturationValue(dialog_id, saturation, value)
+                if dialog_id == *id =>
+            {
+                self.hsv.saturation = saturation;
+                self.hsv.value = value;
+                self.apply_hsv();
+                self.refresh_inputs();
+            }
+            ColorDialogMessage::Hue(dialog_id, hue) if dialog_id == *id => {
+                self.hsv.hue = hue;
+                self.apply_hsv();
+                self.refresh_inputs();
+            }
+            ColorDialogMessage::Alpha(dialog_id, alpha) if dialog_id == *id => {
+                self.color.a = alpha;
+                self.refresh_hex();
+            }
+            ColorDialogMessage::Channel(dialog_id, index, value)
+                if dialog_id == *id && index < 3 =>
+            {
+                let channel = value.trim().parse::<u8>();
+                if value.is_empty() || channel.is_ok() {
+                    self.rgb_input[index] = value;
+                }
+                if let Ok(channel) = channel {
+                    let mut rgba = self.color.into_rgba8();
+                    rgba[index] = channel;
+                    self.set_color(Color::from_rgba8(rgba[0], rgba[1], rgba[2], self.color.a));
+                    self.refresh_hex();
+                }
+            }
+            ColorDialogMessage::Hex(dialog_id, value) if dialog_id == *id => {
+                let hex = match value.starts_with('#') {
+                    true => value.clone(),
+                    false => format!("#{}", value),
+                };
+                self.hex_input = value;
+                if let Some(mut color) = Color::parse(&hex) {
+                    if !self.alpha {
+                        color.a = 1.0;
+                    }
+                    self.set_color(color);
+                    self.refresh_rgb();
+                }
+            }
+            ColorDialogMessage::Picked(dialog_id, mut color) if dialog_id == *id => {
+                if !self.alpha {
+                    color.a = 1.0;
+                }
+                self.set_color(color);
+                self.refresh_inputs();
+            }
+            ColorDialogMessage::Accept(dialog_id) if dialog_id == *id => {
+                push_recent(&mut self.recent, self.color);
+                return EventMessage::Close(*id, self.ok.message.clone()).into();
+            }
+            _ => {}
+        }
+        Message::None
+    }
+
+    fn on_close_request(&self, id: window::Id) -> Message {
+        EventMessage::Close(id, self.cancel.message.clone()).into()
+    }
+
+    fn view(&self, window: &Window, id: window::Id) -> Element<'_, Message> {
+        let square = SaturationValue::new(self.hsv, move |saturation, value| {
+            ColorDialogMessage::SaturationValue(id, saturation, value).into()
+        });
+
+        // * the hue strip above the slider
+        let hue_gradient = (0..=6).fold(Linear::new(Degrees(90.0)), |gradient, step| {
+            let hue = Hsv {
+                hue: (step * 60 % 360) as f32,
+                saturation: 1.0,
+                value: 1.0,
+            };
+            gradient.add_stop(step as f32 / 6.0, hue.to_color(1.0))
+        });
+        let hue = column![
+            container("")
+                .width(Length::Fill)
+                .height(8)
+                .style(move |_: &Theme| container::Style {
+                    background: Some(Background::Gradient(Gradient::Linear(hue_gradient))),
+                    ..Default::default()
+                }),
+            slider(0.0..=359.0, self.hsv.hue, move |hue| {
+                ColorDialogMessage::Hue(id, hue).into()
+            }),
+        ]
+        .spacing(2);
+
+        let alpha = self.alpha.then(|| {
+            row![
+                text("透明").width(40),
+                slider(0.0..=1.0, self.color.a, move |alpha| {
+                    ColorDialogMessage::Alpha(id, alpha).into()
+                })
+                .step(0.01),
+                text(format!("{:.0}%", self.color.a * 100.0)).width(40),
+            ]
+            .spacing(5)
+            .align_y(Alignment::Center)
+        });
+
+        // * the new color beside the initial color
+        let preview = row![swatch(self.initial, None), swatch(self.color, None)];
+
+        let inputs = column![
+            preview,
+            self.input("红", &self.rgb_input[0], move |value| {
+                ColorDialogMessage::Channel(id, 0, value).into()
+            }),
+            self.input("绿", &self.rgb_input[1], move |value| {
+                ColorDialogMessage::Channel(id, 1, value).into()
+            }),
+            self.input("蓝", &self.rgb_input[2], move |value| {
+                ColorDialogMessage::Channel(id, 2, value).into()
+            }),
+            self.input("HEX", &self.hex_input, move |value| {
+                ColorDialogMessage::Hex(id, value).into()
+            }),
+        ]
+        .spacing(8)
+        .width(140);
+
+        let picker = row![column![square, hue].spacing(10).width(Length::Fill), inputs]
+            .spacing(15)
+            .height(Length::Fill);
+
+        let theme_colors = match &self.swatches {
+            Some(colors) => swatches(id, colors),
+            None => swatches(id, &theme_swatches(window.theme())),
+        };
+        let palette = column![text("主题颜色"), theme_colors]
+            .push_maybe(
+                (!self.recent.is_empty())
+                    .then(|| column![text("最近使用"), swatches(id, &self.recent)].spacing(5)),
+            )
+            .spacing(5);
+
+        let button_ok = self
+            .ok
+            .to_button()
+            .on_press(ColorDialogMessage::Accept(id).into())
+            .style(button::primary);
+
+        let button_cancel = self
+            .cancel
+            .to_button()
+            .on_press(EventMessage::Close(id, self.cancel.message.clone()).into())
+            .style(button::secondary);
+
+        let button_group = row![button_ok, button_cancel]
+            .align_y(Alignment::Center)
+            .spacing(20);
+
+        let button_layout = column![button_group]
+            .align_x(Alignment::End)
+            .width(Length::Fill);
+
+        column![picker]
+            .push_maybe(alpha)
+            .push(palette)
+            .push(button_layout)
+            .spacing(10)
+            .padding(10)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
+
+/// ! the color dialog builder
+pub struct ColorDialog {
+    data: ColorDialogData,
+    callback_closed: Option<WindowCloseCallback>,
+}
+
+#[allow(unused)]
+impl ColorDialog {
+    /// create a new color dialog with the title
+    /// * `title` - the title of the dialog window
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            data: ColorDialogData {
+                title: title.into(),
+                ..Default::default()
+            },
+            callback_closed: None,
+        }
+    }
+
+    /// set the initial color
+    pub fn color(mut self, color: Color) -> Self {
+        self.data.color = color;
+        self.data.initial = color;
+        self.data.hsv = Hsv::from_color(color);
+        self
+    }
+
+    /// whether the alpha can be edited, default is true
+    pub fn alpha(mut self, alpha: bool) -> Self {
+        self.data.alpha = alpha;
+        self
+    }
+
+    /// show the swatches of the theme palette, default is the theme of the program
+    pub fn theme(mut self, theme: &Theme) -> Self {
+        self.data.swatches = Some(theme_swatches(theme));
+        self
+    }
+
+    /// set the recent colors, the latest first
+    /// * pass `ColorDialogData::recent` of the last dialog to keep them
+    pub fn recent_colors(mut self, colors: Vec<Color>) -> Self {
+        self.data.recent = colors;
+        self.data.recent.truncate(RECENT_LIMIT);
+        self
+    }
+
+    /// set the size of the dialog
+    pub fn size(mut self, size: iced::Size) -> Self {
+        self.data.window_settings.size = size;
+        self
+    }
+
+    /// set the ok button, the dialog result should be `DialogResult::Ok`
+    pub fn ok_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.ok = button;
+        self
+    }
+
+    /// set the cancel button, the dialog result should be `DialogResult::Cancel`
+    pub fn cancel_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.cancel = button;
+        self
+    }
+
+    /// set the callback function when the dialog is closed
+    /// * get the chosen color by `get_dialog_data::<ColorDialogData>()`
+    pub fn on_closed(mut self, callback: WindowCloseCallback) -> Self {
+        self.callback_closed = Some(callback);
+        self
+    }
+
+    /// show the color dialog window
+    /// * `id` - current window id, this will be a parent id for the new window
+    pub fn show(&self, id: iced::window::Id) -> Message {
+        let mut data = self.data.clone();
+        if !data.alpha {
+            data.color.a = 1.0;
+        }
+        data.refresh_inputs();
+        if let Some(callback) = &self.callback_closed {
+            Message::show_dialog(id, data, *callback)
+        } else {
+            Message::show_dialog(id, data, |_| Message::None)
+        }
+    }
+}
+
+// ! the HSV square
+/// pick the saturation by x and the value by y of the current hue
+struct SaturationValue<'a, Message> {
+    hsv: Hsv,
+    on_change: Box<dyn Fn(f32, f32) -> Message + 'a>,
+}
+
+#[derive(Debug, Default)]
+struct SaturationValueState {
+    dragging: bool,
+}
+
+impl<'a, Message> SaturationValue<'a, Message> {
+    fn new(hsv: Hsv, on_change: impl Fn(f32, f32) -> Message + 'a) -> Self {
+        Self {
+            hsv,
+            on_change: Box::new(on_change),
+        }
+    }
+
+    /// the saturation and the value at the cursor
+    fn pick(&self, bounds: Rectangle, cursor: iced::Point) -> Message {
+        let saturation = ((cursor.x - bounds.x) / bounds.width).clamp(0.0, 1.0);
+        let value = 1.0 - ((cursor.y - bounds.y) / bounds.height).clamp(0.0, 1.0);
+        (self.on_change)(saturation, value)
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer> for SaturationValue<'a, Message>
+where
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<SaturationValueState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(SaturationValueState::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fill)
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::atomic(limits, Length::Fill, Length::Fill)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<SaturationValueState>();
+        let bounds = layout.bounds();
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if let Some(position) = cursor.position_over(bounds) {
+                    state.dragging = true;
+                    shell.publish(self.pick(bounds, position));
+                    return event::Status::Captured;
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) if state.dragging => {
+                shell.publish(self.pick(bounds, position));
+                return event::Status::Captured;
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) if state.dragging => {
+                state.dragging = false;
+                return event::Status::Captured;
+            }
+            _ => {}
+        }
+        event::Status::Ignored
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let hue = Hsv {
+            hue: self.hsv.hue,
+            saturation: 1.0,
+            value: 1.0,
+        }
+        .to_color(1.0);
+        // * the saturation from left to right, then the value from top to bottom
+        let saturation = Linear::new(Degrees(90.0))
+            .add_stop(0.0, Color::WHITE)
+            .add_stop(1.0, hue);
+        let value = Linear::new(Degrees(180.0))
+            .add_stop(0.0, Color::TRANSPARENT)
+            .add_stop(1.0, Color::BLACK);
+        for gradient in [saturation, value] {
+            renderer.fill_quad(
+                Quad {
+                    bounds,
+                    ..Default::default()
+                },
+                Background::Gradient(Gradient::Linear(gradient)),
+            );
+        }
+
+        // * the marker of the current color
+        let marker = Rectangle {
+            x: bounds.x + self.hsv.saturation * bounds.width - 6.0,
+            y: bounds.y + (1.0 - self.hsv.value) * bounds.height - 6.0,
+            width: 12.0,
+            height: 12.0,
+        };
+        renderer.fill_quad(
+            Quad {
+                bounds: marker,
+                border: Border {
+                    color: match self.hsv.value > 0.5 && self.hsv.saturation < 0.5 {
+                        true => Color::BLACK,
+                        false => Color::WHITE,
+                    },
+                    width: 2.0,
+                    radius: 6.0.into(),
+                },
+                ..Default::default()
+            },
+            Color::TRANSPARENT,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<SaturationValueState>();
+        if state.dragging || cursor.is_over(layout.bounds()) {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<SaturationValue<'a, Message>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Renderer: 'a + renderer::Renderer,
+{
+    fn from(square: SaturationValue<'a, Message>) -> Self {
+        Self::new(square)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Color, b: Color) {
+        let [a, b] = [a, b].map(|color| [color.r, color.g, color.b, color.a]);
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).abs() < 1e-4, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn hsv_round_trip() {
+        for rgb in [
+            [0, 0, 0],
+            [255, 255, 255],
+            [255, 0, 0],
+            [0, 255, 0],
+            [0, 0, 255],
+            [255, 255, 0],
+            [18, 52, 86],
+            [200, 100, 150],
+        ] {
+            let color = Color::from_rgb8(rgb[0], rgb[1], rgb[2]);
+            assert_close(Hsv::from_color(color).to_color(1.0), color);
+        }
+    }
+
+    #[test]
+    fn hsv_of_primary_colors() {
+        let hsv = Hsv::from_color(Color::from_rgb(0.0, 0.0, 1.0));
+        assert_eq!((hsv.hue, hsv.saturation, hsv.value), (240.0, 1.0, 1.0));
+        // * the gray has no hue
+        let hsv = Hsv::from_color(Color::from_rgb(0.5, 0.5, 0.5));
+        assert_eq!((hsv.hue, hsv.saturation, hsv.value), (0.0, 0.0, 0.5));
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let color = Color::from_rgb8(0x12, 0x34, 0xAB);
+        assert_eq!(to_hex(color), "#1234AB");
+        assert_eq!(Color::parse(&to_hex(color)), Some(color));
+        let color = Color::from_rgba8(0x12, 0x34, 0xAB, 0x80 as f32 / 255.0);
+        assert_eq!(to_hex(color), "#1234AB80");
+        assert_close(Color::parse(&to_hex(color)).unwrap(), color);
+    }
+
+    #[test]
+    fn recent_colors_latest_first() {
+        let mut colors = Vec::new();
+        for value in 0..=RECENT_LIMIT as u8 {
+            push_recent(&mut colors, Color::from_rgb8(value, 0, 0));
+        }
+        push_recent(&mut colors, Color::from_rgb8(5, 0, 0));
+        assert_eq!(colors.len(), RECENT_LIMIT);
+        assert_eq!(colors[0], Color::from_rgb8(5, 0, 0));
+        assert_eq!(colors.iter().filter(|c| **c == colors[0]).count(), 1);
+    }
+}
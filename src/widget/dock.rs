@@ -0,0 +1,443 @@
+// ! a docking layout host for tool panels
+// ! every panel is an [IWindow], so it can be docked in a window or floated into a new window
+
+use iced::widget::pane_grid::{self, Axis, Configuration, DragEvent, Node, Pane, ResizeEvent};
+use iced::widget::{button, container, row, text, PaneGrid};
+use iced::{window, Element, Length, Theme};
+use serde::{Deserialize, Serialize};
+
+use crate::core::{IWindow, IWindowMessage, Message, Window, WindowData};
+
+/// create a new dock with the first panel
+/// * `panel` - the first panel of the dock
+pub fn dock(panel: DockPanel) -> Dock {
+    Dock::new(panel)
+}
+
+/// a tool panel hosted by the [Dock]
+#[derive(Debug, Clone)]
+pub struct DockPanel {
+    /// the unique key of the panel, it is used to save and restore the layout
+    pub key: String,
+    /// the title shown in the panel title bar
+    pub title: String,
+    /// the panel data, it will be opened as a new window when floated
+    pub data: Box<dyn IWindow>,
+}
+
+impl DockPanel {
+    /// create a new panel
+    /// * `key` - the unique key of the panel
+    /// * `title` - the title of the panel
+    /// * `data` - the panel data
+    pub fn new<T>(key: impl Into<String>, title: impl Into<String>, data: T) -> Self
+    where
+        T: IWindow,
+    {
+        Self {
+            key: key.into(),
+            title: title.into(),
+            data: Box::new(data),
+        }
+    }
+}
+
+/// the dock message, the window id is the host window of the dock
+#[derive(Debug, Clone)]
+pub enum DockMessage {
+    /// a panel was clicked
+    Clicked(window::Id, Pane),
+    /// a panel was dragged
+    Dragged(window::Id, DragEvent),
+    /// a split was resized
+    Resized(window::Id, ResizeEvent),
+    /// maximize a panel
+    Maximize(window::Id, Pane),
+    /// restore the maximized panel
+    Restore(window::Id),
+    /// close a panel
+    Close(window::Id, Pane),
+    /// float a panel into a new window
+    Float(window::Id, Pane),
+    /// dock a panel again, e.g. the floating window was refused
+    Docked(window::Id, DockPanel),
+}
+
+impl IWindowMessage for DockMessage {}
+
+/// the split direction of a [DockLayout]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DockAxis {
+    Horizontal,
+    Vertical,
+}
+
+impl From<Axis> for DockAxis {
+    fn from(value: Axis) -> Self {
+        match value {
+            Axis::Horizontal => DockAxis::Horizontal,
+            Axis::Vertical => DockAxis::Vertical,
+        }
+    }
+}
+
+impl From<DockAxis> for Axis {
+    fn from(value: DockAxis) -> Self {
+        match value {
+            DockAxis::Horizontal => Axis::Horizontal,
+            DockAxis::Vertical => Axis::Vertical,
+        }
+    }
+}
+
+/// the serializable layout of a [Dock]
+/// * save it by [Dock::layout] and restore it by [Dock::restore]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DockLayout {
+    /// a split of two layouts
+    Split {
+        axis: DockAxis,
+        ratio: f32,
+        a: Box<DockLayout>,
+        b: Box<DockLayout>,
+    },
+    /// a panel by its key
+    Panel(String),
+}
+
+/// ! the dock
+/// * call [Dock::update] in the host window update
+/// * call [Dock::view] in the host window view
+#[derive(Debug, Clone)]
+pub struct Dock {
+    panes: pane_grid::State<DockPanel>,
+    focus: Option<Pane>,
+}
+
+impl Dock {
+    /// create a new dock with the first panel
+    pub fn new(panel: DockPanel) -> Self {
+        let (panes, pane) = pane_grid::State::new(panel);
+        Self {
+            panes,
+            focus: Some(pane),
+        }
+    }
+
+    /// restore a dock from a saved layout
+    /// * `layout` - the saved layout
+    /// * `create` - create the panel by its key, the panel is skipped when it returns `None`
+    pub fn restore(
+        layout: &DockLayout,
+        create: impl Fn(&str) -> Option<DockPanel>,
+    ) -> Option<Self> {
+        let configuration = to_configuration(layout, &create)?;
+        Some(Self {
+            panes: pane_grid::State::with_configuration(configuration),
+            focus: None,
+        })
+    }
+
+    /// get the current layout of the dock, you can serialize it and restore it next launch
+    pub fn layout(&self) -> DockLayout {
+        self.to_layout(self.panes.layout())
+    }
+
+    /// split a panel and put the new panel beside it
+    /// * `pane` - the panel to split
+    /// * `axis` - the split direction
+    /// * `panel` - the new panel
+    pub fn split(&mut self, pane: Pane, axis: Axis, panel: DockPanel) -> Option<Pane> {
+        let (pane, _) = self.panes.split(axis, pane, panel)?;
+        self.focus = Some(pane);
+        Some(pane)
+    }
+
+    /// add a new panel beside the focused panel
+    pub fn add(&mut self, axis: Axis, panel: DockPanel) -> Option<Pane> {
+        let pane = self
+            .focus
+            .or_else(|| self.panes.iter().map(|(pane, _)| *pane).next())?;
+        self.split(pane, axis, panel)
+    }
+
+    /// get the focused panel
+    pub fn focus(&self) -> Option<Pane> {
+        self.focus
+    }
+
+    /// get the panels of the dock
+    pub fn panels(&self) -> impl Iterator<Item = (&Pane, &DockPanel)> {
+        self.panes.iter()
+    }
+
+    /// find the panel by its key
+    pub fn find(&self, key: &str) -> Option<Pane> {
+        self.panes
+            .iter()
+            .find(|(_, panel)| panel.key == key)
+            .map(|(pane, _)| *pane)
+    }
+
+    /// handle the dock message and forward other messages to every panel
+    /// # Arguments
+    /// * `id` - the host window id
+    /// * `message` - the message from the host window update
+    #[allow(clippy::borrowed_box)]
+    pub fn update(&mut self, id: &window::Id, message: &Box<dyn IWindowMessage>) -> Message {
+        let Some(msg) = message.downcast_ref::<DockMessage>() else {
+            let msgs: Vec<Message> = self
+                .panes
+                .iter_mut()
+                .map(|(_, panel)| panel.data.update(id, message))
+                .filter(|msg| !matches!(msg, Message::None))
+                .collect();
+            return msgs.into();
+        };
+        match msg.clone() {
+            DockMessage::Clicked(host, pane) if host == *id => {
+                self.focus = Some(pane);
+            }
+            DockMessage::Dragged(host, DragEvent::Dropped { pane, target }) if host == *id => {
+                self.panes.drop(pane, target);
+            }
+            DockMessage::Resized(host, ResizeEvent { split, ratio }) if host == *id => {
+                self.panes.resize(split, ratio);
+            }
+            DockMessage::Maximize(host, pane) if host == *id => {
+                self.panes.maximize(pane);
+            }
+            DockMessage::Restore(host) if host == *id => {
+                self.panes.restore();
+            }
+            DockMessage::Close(host, pane) if host == *id => {
+                if let Some((_, sibling)) = self.panes.close(pane) {
+                    self.focus = Some(sibling);
+                }
+            }
+            DockMessage::Float(host, pane) if host == *id => {
+                if let Some((panel, sibling)) = self.panes.close(pane) {
+                    self.focus = Some(sibling);
+                    let DockPanel { key, title, data } = panel;
+                    // * the floating panel doesn't block the host, and it goes back when refused
+                    let data = WindowData::from_box(Some(host), data)
+                        .modeless()
+                        .on_refused(move |data| {
+                            DockMessage::Docked(
+                                host,
+                                DockPanel {
+                                    key: key.clone(),
+                                    title: title.clone(),
+                                    data,
+                                },
+                            )
+                            .into()
+                        });
+                    return Message::NewWindow(data);
+                }
+            }
+            DockMessage::Docked(host, panel) if host == *id => {
+                self.add(Axis::Vertical, panel);
+            }
+            _ => {}
+        }
+        Message::None
+    }
+
+    /// get the view of the dock
+    /// # Arguments
+    /// * `window` - the host window
+    /// * `id` - the host window id
+    pub fn view<'a>(&'a self, window: &'a Window, id: window::Id) -> Element<'a, Message> {
+        let total = self.panes.len();
+        PaneGrid::new(&self.panes, move |pane, panel, is_maximized| {
+            let focused = self.focus == Some(pane);
+            let title = text(&panel.title).size(14);
+
+            let mut controls = row![].spacing(5);
+            controls = controls.push(
+                button(text("浮动").size(12))
+                    .padding([2, 6])
+                    .style(button::secondary)
+                    .on_press_maybe((total > 1).then(|| DockMessage::Float(id, pane).into())),
+            );
+            controls = controls.push(if is_maximized {
+                button(text("还原").size(12))
+                    .padding([2, 6])
+                    .style(button::secondary)
+                    .on_press(DockMessage::Restore(id).into())
+            } else {
+                button(text("最大化").size(12))
+                    .padding([2, 6])
+                    .style(button::secondary)
+                    .on_press_maybe((total > 1).then(|| DockMessage::Maximize(id, pane).into()))
+            });
+            controls = controls.push(
+                button(text("关闭").size(12))
+                    .padding([2, 6])
+                    .style(button::danger)
+                    .on_press_maybe((total > 1).then(|| DockMessage::Close(id, pane).into())),
+            );
+
+            let title_bar = pane_grid::TitleBar::new(title)
+                .controls(pane_grid::Controls::new(controls))
+                .padding(5)
+                .style(move |theme| title_bar_style(theme, focused));
+
+            pane_grid::Content::new(
+                container(panel.data.view(window, id))
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .padding(5),
+            )
+            .title_bar(title_bar)
+            .style(move |theme| pane_style(theme, focused))
+        })
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .spacing(5)
+        .on_click(move |pane| DockMessage::Clicked(id, pane).into())
+        .on_drag(move |event| DockMessage::Dragged(id, event).into())
+        .on_resize(10, move |event| DockMessage::Resized(id, event).into())
+        .into()
+    }
+
+    /// convert the pane grid node to the layout
+    fn to_layout(&self, node: &Node) -> DockLayout {
+        match node {
+            Node::Split {
+                axis, ratio, a, b, ..
+            } => DockLayout::Split {
+                axis: (*axis).into(),
+                ratio: *ratio,
+                a: Box::new(self.to_layout(a)),
+                b: Box::new(self.to_layout(b)),
+            },
+            Node::Pane(pane) => DockLayout::Panel(
+                self.panes
+                    .get(*pane)
+                    .map(|panel| panel.key.clone())
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+/// convert the layout to the pane grid configuration, the missing panels are removed
+fn to_configuration(
+    layout: &DockLayout,
+    create: &impl Fn(&str) -> Option<DockPanel>,
+) -> Option<Configuration<DockPanel>> {
+    match layout {
+        DockLayout::Split { axis, ratio, a, b } => {
+            match (to_configuration(a, create), to_configuration(b, create)) {
+                (Some(a), Some(b)) => Some(Configuration::Split {
+                    axis: (*axis).into(),
+                    ratio: *ratio,
+                    a: Box::new(a),
+                    b: Box::new(b),
+                }),
+                (Some(a), None) => Some(a),
+                (None, b) => b,
+            }
+        }
+        DockLayout::Panel(key) => create(key).map(Configuration::Pane),
+    }
+}
+
+/// the style of the panel title bar
+fn title_bar_style(theme: &Theme, focused: bool) -> container::Style {
+    let palette = theme.extended_palette();
+    let pair = if focused {
+        palette.primary.strong
+    } else {
+        palette.background.strong
+    };
+    container::Style {
+        text_color: Some(pair.text),
+        background: Some(pair.color.into()),
+        ..Default::default()
+    }
+}
+
+/// the style of the panel
+fn pane_style(theme: &Theme, focused: bool) -> container::Style {
+    let palette = theme.extended_palette();
+    container::Style {
+        background: Some(palette.background.weak.color.into()),
+        border: iced::Border {
+            width: 2.0,
+            color: if focused {
+                palette.primary.strong.color
+            } else {
+                palette.background.strong.color
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct Tool;
+
+    impl IWindow for Tool {
+        fn new_window(&self) -> Window {
+            Window::builder(self.clone()).build()
+        }
+
+        fn view(&self, _window: &Window, _id: window::Id) -> Element<'_, Message> {
+            text("").into()
+        }
+    }
+
+    fn layout() -> DockLayout {
+        DockLayout::Split {
+            axis: DockAxis::Vertical,
+            ratio: 0.3,
+            a: Box::new(DockLayout::Panel("files".into())),
+            b: Box::new(DockLayout::Split {
+                axis: DockAxis::Horizontal,
+                ratio: 0.7,
+                a: Box::new(DockLayout::Panel("editor".into())),
+                b: Box::new(DockLayout::Panel("console".into())),
+            }),
+        }
+    }
+
+    #[test]
+    fn layout_serde_round_trip() {
+        let layout = layout();
+        let json = serde_json::to_string(&layout).unwrap();
+        assert_eq!(serde_json::from_str::<DockLayout>(&json).unwrap(), layout);
+    }
+
+    #[test]
+    fn restore_layout() {
+        let layout = layout();
+        let dock = Dock::restore(&layout, |key| Some(DockPanel::new(key, key, Tool))).unwrap();
+        assert_eq!(dock.layout(), layout);
+    }
+
+    #[test]
+    fn restore_without_missing_panels() {
+        let dock = Dock::restore(&layout(), |key| {
+            (key != "console").then(|| DockPanel::new(key, key, Tool))
+        })
+        .unwrap();
+        assert_eq!(
+            dock.layout(),
+            DockLayout::Split {
+                axis: DockAxis::Vertical,
+                ratio: 0.3,
+                a: Box::new(DockLayout::Panel("files".into())),
+                b: Box::new(DockLayout::Panel("editor".into())),
+            }
+        );
+        assert!(Dock::restore(&layout(), |_| None).is_none());
+    }
+}
@@ -0,0 +1,806 @@
+// ! file dialogs like winform, implemented by iced and std::fs without native dependency
+// ! get the chosen paths in the close callback by `get_dialog_data::<FileDialogData>()`
+
+use iced::widget::{button, checkbox, column, pick_list, row, text, text_input};
+use iced::window::Level;
+use iced::{window, Alignment, Element, Length, Point, Size};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::job::BackgroundWork;
+use super::message_box::message_box;
+use super::table::{table, table_head, TableHead, TableRow};
+use super::{message_box_button, MessageBoxButton};
+use crate::core::{
+    DialogResult, EventMessage, IWindow, IWindowMessage, Message, Window, WindowCloseCallback,
+    WindowCloseCallbackData, WindowPlacement,
+};
+
+/// the max interval of the two clicks of a double click
+const DOUBLE_CLICK: Duration = Duration::from_millis(500);
+
+/// the dialog to choose files to open
+pub type OpenFileDialog = FileDialog<OpenFile>;
+/// the dialog to choose a file to save
+pub type SaveFileDialog = FileDialog<SaveFile>;
+/// the dialog to choose a folder
+pub type FolderBrowserDialog = FileDialog<FolderBrowser>;
+
+/// the kind of the [FileDialog]
+pub trait FileDialogKind {
+    const MODE: FileDialogMode;
+}
+
+/// the marker of [OpenFileDialog]
+pub struct OpenFile;
+/// the marker of [SaveFileDialog]
+pub struct SaveFile;
+/// the marker of [FolderBrowserDialog]
+pub struct FolderBrowser;
+
+impl FileDialogKind for OpenFile {
+    const MODE: FileDialogMode = FileDialogMode::Open;
+}
+
+impl FileDialogKind for SaveFile {
+    const MODE: FileDialogMode = FileDialogMode::Save;
+}
+
+impl FileDialogKind for FolderBrowser {
+    const MODE: FileDialogMode = FileDialogMode::Folder;
+}
+
+/// what the file dialog chooses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileDialogMode {
+    Open,
+    Save,
+    Folder,
+}
+
+/// a file filter by the extensions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFilter {
+    pub name: String,
+    /// the extensions without dot, empty means all files
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    /// create a filter
+    /// * `name` - the filter name
+    /// * `extensions` - the extensions without dot, like `["png", "jpg"]`
+    pub fn new(name: impl Into<String>, extensions: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            extensions: extensions.iter().map(|ext| ext.to_lowercase()).collect(),
+        }
+    }
+
+    /// the filter of all files
+    pub fn all() -> Self {
+        Self::new("所有文件", &[])
+    }
+
+    /// whether the file matches the filter
+    pub fn matches(&self, path: &Path) -> bool {
+        if self.extensions.is_empty() {
+            return true;
+        }
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.extensions.contains(&ext.to_lowercase()))
+    }
+}
+
+impl std::fmt::Display for FileFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.extensions.is_empty() {
+            true => write!(f, "{} (*.*)", self.name),
+            false => {
+                let extensions: Vec<String> = self
+                    .extensions
+                    .iter()
+                    .map(|ext| format!("*.{}", ext))
+                    .collect();
+                write!(f, "{} ({})", self.name, extensions.join(";"))
+            }
+        }
+    }
+}
+
+/// a file or folder in the listing
+#[derive(Debug, Clone)]
+pub struct FileEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub is_dir: bool,
+    /// the file size in bytes, `None` for the folder
+    pub size: Option<u64>,
+    selected: bool,
+}
+
+impl TableRow for FileEntry {
+    fn get_value(&self, filed_name: &str) -> String {
+        match filed_name {
+            "selected" => if self.selected { "√" } else { "" }.into(),
+            "name" => self.name.clone(),
+            "kind" => match (self.is_dir, self.path.extension()) {
+                (true, _) => "文件夹".into(),
+                (false, Some(ext)) => format!("{} 文件", ext.to_string_lossy().to_uppercase()),
+                (false, None) => "文件".into(),
+            },
+            "size" => self.size.map(format_size).unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+}
+
+/// the readable file size
+fn format_size(size: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} {}", size, UNITS[0]),
+        _ => format!("{:.1} {}", value, UNITS[unit]),
+    }
+}
+
+/// the file dialog message, the window id is the file dialog window
+#[derive(Debug, Clone)]
+pub enum FileDialogMessage {
+    PathChanged(window::Id, String),
+    PathSubmitted(window::Id),
+    Up(window::Id),
+    Clicked(window::Id, i32),
+    FileNameChanged(window::Id, String),
+    FilterSelected(window::Id, FileFilter),
+    HiddenToggled(window::Id, bool),
+    Accept(window::Id),
+    /// the user confirmed to overwrite the existing file
+    Overwrite(window::Id),
+    /// the directory is listed in the background, the number is the loading sequence
+    Loaded(window::Id, u64, Result<Vec<FileEntry>, String>),
+}
+
+impl IWindowMessage for FileDialogMessage {}
+
+/// ! the file dialog window data
+#[derive(Debug, Clone)]
+pub struct FileDialogData {
+    pub title: String,
+    pub mode: FileDialogMode,
+    /// the current directory
+    pub directory: PathBuf,
+    pub filters: Vec<FileFilter>,
+    pub filter: Option<FileFilter>,
+    pub show_hidden: bool,
+    /// only for the open file dialog
+    pub multiselect: bool,
+    /// only for the save file dialog
+    pub overwrite_prompt: bool,
+    /// the file name of the save file dialog
+    pub file_name: String,
+    /// ! the chosen paths when the dialog is closed with `DialogResult::Ok`
+    pub paths: Vec<PathBuf>,
+    pub ok: MessageBoxButton,
+    pub cancel: MessageBoxButton,
+    pub window_settings: iced::window::Settings,
+    /// the text of the path bar
+    path_input: String,
+    entries: Vec<FileEntry>,
+    heads: Vec<TableHead>,
+    error: Option<String>,
+    last_click: Option<(usize, Instant)>,
+    /// the sequence of the latest loading, the earlier listings are dropped
+    loading: u64,
+    /// whether the directory is being listed
+    is_loading: bool,
+}
+
+impl Default for FileDialogData {
+    fn default() -> Self {
+        let directory = std::env::current_dir().unwrap_or_default();
+        Self {
+            title: "打开".into(),
+            mode: FileDialogMode::Open,
+            path_input: directory.display().to_string(),
+            directory,
+            filters: Vec::new(),
+            filter: None,
+            show_hidden: false,
+            multiselect: false,
+            overwrite_prompt: true,
+            file_name: String::new(),
+            paths: Vec::new(),
+            ok: message_box_button("确定").dialog_result(DialogResult::Ok),
+            cancel: message_box_button("取消").dialog_result(DialogResult::Cancel),
+            window_settings: iced::window::Settings {
+                min_size: Some(iced::Size::new(560.0, 400.0)),
+                size: iced::Size::new(720.0, 480.0),
+                position: iced::window::Position::Centered,
+                level: Level::AlwaysOnTop,
+                resizable: true,
+                icon: None,
+                ..Default::default()
+            },
+            entries: Vec::new(),
+            heads: vec![
+                table_head("selected", "").width(30),
+                table_head("name", "名称").align_x(Alignment::Start),
+                table_head("kind", "类型").width(100),
+                table_head("size", "大小")
+                    .width(100)
+                    .align_x(Alignment::End),
+            ],
+            error: None,
+            last_click: None,
+            loading: 0,
+            is_loading: true,
+        }
+    }
+}
+
+/// list the directory, the folders first, then by the name
+/// * `filter` - the files not matched are skipped, the folders are always listed
+fn read_entries(
+    directory: &Path,
+    mode: FileDialogMode,
+    filter: Option<&FileFilter>,
+    show_hidden: bool,
+) -> std::io::Result<Vec<FileEntry>> {
+    let mut entries: Vec<FileEntry> = std::fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !show_hidden && is_hidden(&name, &entry.metadata().ok()?) {
+                return None;
+            }
+            let path = entry.path();
+            let metadata = std::fs::metadata(&path).ok()?;
+            let is_dir = metadata.is_dir();
+            let visible = match (mode, is_dir) {
+                (_, true) => true,
+                (FileDialogMode::Folder, false) => false,
+                (_, false) => filter.map_or(true, |f| f.matches(&path)),
+            };
+            visible.then_some(FileEntry {
+                name,
+                path,
+                is_dir,
+                size: (!is_dir).then_some(metadata.len()),
+                selected: false,
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        b.is_dir
+            .cmp(&a.is_dir)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    Ok(entries)
+}
+
+/// whether the file is hidden, by the attribute on windows or the leading dot on the others
+#[cfg(windows)]
+fn is_hidden(_name: &str, metadata: &std::fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+    metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0
+}
+
+/// whether the file is hidden, by the attribute on windows or the leading dot on the others
+#[cfg(not(windows))]
+fn is_hidden(name: &str, _metadata: &std::fs::Metadata) -> bool {
+    name.starts_with('.')
+}
+
+impl FileDialogData {
+    /// the chosen path, the first one for the open file dialog
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.paths.first()
+    }
+
+    /// go to the directory and list it
+    fn navigate(&mut self, id: window::Id, directory: PathBuf) -> Message {
+        self.directory = directory;
+        self.path_input = self.directory.display().to_string();
+        self.last_click = None;
+        self.load(id)
+    }
+
+    /// list the current directory again, e.g. the filter or the hidden toggle changed
+    fn load(&mut self, id: window::Id) -> Message {
+        self.loading += 1;
+        self.is_loading = true;
+        self.error = None;
+        self.list(id)
+    }
+
+    /// list the current directory in the background by the filter and the hidden toggle
+    fn list(&self, id: window::Id) -> Message {
+        let loading = self.loading;
+        let directory = self.directory.clone();
+        let (mode, filter, show_hidden) = (self.mode, self.filter.clone(), self.show_hidden);
+        // * the listing is not a user job, it isn't shown by `Window::running_jobs`
+        BackgroundWork::start(id, move || {
+            let entries = read_entries(&directory, mode, filter.as_ref(), show_hidden)
+                .map_err(|e| e.to_string());
+            FileDialogMessage::Loaded(id, loading, entries)
+        })
+    }
+
+    /// select the clicked entry, a double click opens the folder or accepts the file
+    fn click(&mut self, id: window::Id, row: usize) -> Message {
+        let Some(entry) = self.entries.get(row).cloned() else {
+            return Message::None;
+        };
+        let double_click = self
+            .last_click
+            .is_some_and(|(last, time)| last == row && time.elapsed() < DOUBLE_CLICK);
+        self.last_click = Some((row, Instant::now()));
+        if double_click {
+            if entry.is_dir {
+                return self.navigate(id, entry.path);
+            }
+            self.select_only(row);
+            return self.accept(id);
+        }
+        if self.multiselect && self.mode == FileDialogMode::Open {
+            if let Some(entry) = self.entries.get_mut(row) {
+                entry.selected = !entry.selected;
+            }
+        } else {
+            self.select_only(row);
+        }
+        // * show the selected file names
+        if self.mode != FileDialogMode::Folder {
+            let names: Vec<String> = self
+                .entries
+                .iter()
+                .filter(|entry| entry.selected && !entry.is_dir)
+                .map(|entry| entry.name.clone())
+                .collect();
+            if !names.is_empty() {
+                self.file_name = names.join("; ");
+            }
+        }
+        Message::None
+    }
+
+    fn select_only(&mut self, row: usize) {
+        for (index, entry) in self.entries.iter_mut().enumerate() {
+            entry.selected = index == row;
+        }
+    }
+
+    /// the selected entries
+    fn selected(&self) -> impl Iterator<Item = &FileEntry> {
+        self.entries.iter().filter(|entry| entry.selected)
+    }
+
+    /// choose the paths and close the dialog
+    fn accept(&mut self, id: window::Id) -> Message {
+        self.error = None;
+        match self.mode {
+            FileDialogMode::Open => {
+                // * open the only selected folder
+                let folder = {
+                    let mut selected = self.selected();
+                    match (selected.next(), selected.next()) {
+                        (Some(entry), None) if entry.is_dir => Some(entry.path.clone()),
+                        _ => None,
+                    }
+                };
+                if let Some(folder) = folder {
+                    return self.navigate(id, folder);
+                }
+                let mut paths: Vec<PathBuf> = self
+                    .selected()
+                    .filter(|entry| !entry.is_dir)
+                    .map(|entry| entry.path.clone())
+                    .collect();
+                // * or the typed file name
+                if paths.is_empty() && !self.file_name.trim().is_empty() {
+                    let path = self.directory.join(self.file_name.trim());
+                    if !path.is_file() {
+                        self.error = Some(format!("文件不存在: {}", path.display()));
+                        return Message::None;
+                    }
+                    paths.push(path);
+                }
+                if paths.is_empty() {
+                    return Message::None;
+                }
+                self.paths = paths;
+                EventMessage::Close(id, self.ok.message.clone()).into()
+            }
+            FileDialogMode::Save => {
+                let name = self.file_name.trim();
+                if name.is_empty() {
+                    self.error = Some("请输入文件名".into());
+                    return Message::None;
+                }
+                let mut path = self.directory.join(name);
+                // * append the extension of the filter when it's missing
+                if path.extension().is_none() {
+                    if let Some([ext]) = self.filter.as_ref().map(|f| f.extensions.as_slice()) {
+                        path.set_extension(ext);
+                    }
+                }
+                if path.is_dir() {
+                    return self.navigate(id, path);
+                }
+                self.paths = vec![path.clone()];
+                if path.exists() && self.overwrite_prompt {
+                    return message_box(
+                        "确认另存为",
+                        format!("{} 已存在。\n要替换它吗?", path.display()),
+                    )
+                    .on_closed(on_overwrite_closed)
+                    .show(id);
+                }
+                EventMessage::Close(id, self.ok.message.clone()).into()
+            }
+            FileDialogMode::Folder => {
+                let path = self
+                    .selected()
+                    .find(|entry| entry.is_dir)
+                    .map(|entry| entry.path.clone())
+                    .unwrap_or_else(|| self.directory.clone());
+                self.paths = vec![path];
+                EventMessage::Close(id, self.ok.message.clone()).into()
+            }
+        }
+    }
+}
+
+/// overwrite the existing file when the user confirmed
+fn on_overwrite_closed(data: WindowCloseCallbackData) -> Message {
+    match data.dialog_result {
+        DialogResult::Ok => Message::send_to(data.id, FileDialogMessage::Overwrite(data.id)),
+        _ => Message::None,
+    }
+}
+
+impl IWindow for FileDialogData {
+    fn new_window(&self) -> Window {
+        Window::builder(self.to_owned())
+            .title(self.title.clone())
+            .settings(self.window_settings.clone())
+            .placement(WindowPlacement::CenteredOnParent)
+            .build()
+    }
+
+    fn update(
+        &mut self,
+        id: &window::Id,
+        message: &std::boxed::Box<dyn IWindowMessage>,
+    ) -> Message {
+        let Some(msg) = message.downcast_ref::<FileDialogMessage>() else {
+            return Message::None;
+        };
+        match msg.clone() {
+            FileDialogMessage::PathChanged(dialog_id, path) if dialog_id == *id => {
+                self.path_input = path;
+            }
+            FileDialogMessage::PathSubmitted(dialog_id) if dialog_id == *id => {
+                let path = PathBuf::from(self.path_input.trim());
+                if path.is_dir() {
+                    return self.navigate(*id, path);
+                } else {
+                    self.error = Some(format!("找不到文件夹: {}", path.display()));
+                }
+            }
+            FileDialogMessage::Up(dialog_id) if dialog_id == *id => {
+                if let Some(parent) = self.directory.parent().map(Path::to_path_buf) {
+                    return self.navigate(*id, parent);
+                }
+            }
+            FileDialogMessage::Clicked(dialog_id, row) if dialog_id == *id && row >= 0 => {
+                return self.click(*id, row as usize);
+            }
+            FileDialogMessage::FileNameChanged(dialog_id, name) if dialog_id == *id => {
+                self.file_name = name;
+            }
+            FileDialogMessage::FilterSelected(dialog_id, filter) if dialog_id == *id => {
+                self.filter = Some(filter);
+                return self.load(*id);
+            }
+            FileDialogMessage::HiddenToggled(dialog_id, show_hidden) if dialog_id == *id => {
+                self.show_hidden = show_hidden;
+                return self.load(*id);
+            }
+            FileDialogMessage::Accept(dialog_id) if dialog_id == *id => {
+                return self.accept(*id);
+            }
+            FileDialogMessage::Overwrite(dialog_id) if dialog_id == *id => {
+                return EventMessage::Close(*id, self.ok.message.clone()).into();
+            }
+            FileDialogMessage::Loaded(dialog_id, loading, entries)
+                if dialog_id == *id && loading == self.loading =>
+            {
+                self.is_loading = false;
+                match entries {
+                    Ok(entries) => self.entries = entries,
+                    Err(e) => {
+                        self.entries.clear();
+                        self.error = Some(e);
+                    }
+                }
+            }
+            _ => {}
+        }
+        Message::None
+    }
+
+    /// list the directory when the dialog is opened
+    fn on_opened(&self, id: window::Id, _position: Option<Point>, _size: Size) -> Message {
+        self.list(id)
+    }
+
+    fn on_close_request(&self, id: window::Id) -> Message {
+        EventMessage::Close(id, self.cancel.message.clone()).into()
+    }
+
+    fn view(&self, _window: &Window, id: window::Id) -> Element<'_, Message> {
+        let path_bar = row![
+            button(text("上级"))
+                .style(button::secondary)
+                .on_press_maybe(
+                    self.directory
+                        .parent()
+                        .map(|_| FileDialogMessage::Up(id).into())
+                ),
+            text_input("", &self.path_input)
+                .on_input(move |path| FileDialogMessage::PathChanged(id, path).into())
+                .on_submit(FileDialogMessage::PathSubmitted(id).into()),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let listing = table(&self.heads, &self.entries)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .on_row_selected(move |row| FileDialogMessage::Clicked(id, row).into());
+
+        let mut options = row![].spacing(10).align_y(Alignment::Center);
+        if self.mode != FileDialogMode::Folder {
+            options = options.push(
+                text_input("文件名", &self.file_name)
+                    .on_input(move |name| FileDialogMessage::FileNameChanged(id, name).into())
+                    .on_submit(FileDialogMessage::Accept(id).into()),
+            );
+            if !self.filters.is_empty() {
+                options = options.push(
+                    pick_list(
+                        self.filters.as_slice(),
+                        self.filter.clone(),
+                        move |filter| FileDialogMessage::FilterSelected(id, filter).into(),
+                    )
+                    .width(220),
+                );
+            }
+        }
+        options = options.push(
+            checkbox("显示隐藏文件", self.show_hidden)
+                .on_toggle(move |show| FileDialogMessage::HiddenToggled(id, show).into()),
+        );
+
+        let loading = self.is_loading.then(|| text("正在加载..."));
+        let error = self.error.as_ref().map(|error| {
+            text(error).style(|theme: &iced::Theme| text::Style {
+                color: Some(theme.extended_palette().danger.base.color),
+            })
+        });
+
+        let button_ok = self
+            .ok
+            .to_button()
+            .on_press(FileDialogMessage::Accept(id).into())
+            .style(button::primary);
+
+        let button_cancel = self
+            .cancel
+            .to_button()
+            .on_press(EventMessage::Close(id, self.cancel.message.clone()).into())
+            .style(button::secondary);
+
+        let button_group = row![button_ok, button_cancel]
+            .align_y(Alignment::Center)
+            .spacing(20);
+
+        let button_layout = column![button_group]
+            .align_x(Alignment::End)
+            .width(Length::Fill);
+
+        column![path_bar, listing, options]
+            .push_maybe(loading)
+            .push_maybe(error)
+            .push(button_layout)
+            .spacing(10)
+            .padding(10)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
+
+/// ! the file dialog builder
+/// * `OpenFileDialog::new(title)` - choose files to open
+/// * `SaveFileDialog::new(title)` - choose a file to save
+/// * `FolderBrowserDialog::new(title)` - choose a folder
+pub struct FileDialog<K> {
+    data: FileDialogData,
+    callback_closed: Option<WindowCloseCallback>,
+    kind: PhantomData<K>,
+}
+
+#[allow(unused)]
+impl<K> FileDialog<K>
+where
+    K: FileDialogKind,
+{
+    /// create a new file dialog with the title
+    /// * `title` - the title of the dialog window
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            data: FileDialogData {
+                title: title.into(),
+                mode: K::MODE,
+                ..Default::default()
+            },
+            callback_closed: None,
+            kind: PhantomData,
+        }
+    }
+
+    /// set the initial directory, default is the current directory
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.data.directory = directory.into();
+        self.data.path_input = self.data.directory.display().to_string();
+        self
+    }
+
+    /// show the hidden files, default is false
+    pub fn show_hidden(mut self, show_hidden: bool) -> Self {
+        self.data.show_hidden = show_hidden;
+        self
+    }
+
+    /// set the size of the dialog
+    pub fn size(mut self, size: iced::Size) -> Self {
+        self.data.window_settings.size = size;
+        self
+    }
+
+    /// set the ok button, the dialog result should be `DialogResult::Ok`
+    pub fn ok_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.ok = button;
+        self
+    }
+
+    /// set the cancel button, the dialog result should be `DialogResult::Cancel`
+    pub fn cancel_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.cancel = button;
+        self
+    }
+
+    /// set the callback function when the dialog is closed
+    /// * get the chosen paths by `get_dialog_data::<FileDialogData>()`
+    pub fn on_closed(mut self, callback: WindowCloseCallback) -> Self {
+        self.callback_closed = Some(callback);
+        self
+    }
+
+    /// show the file dialog window
+    /// * `id` - current window id, this will be a parent id for the new window
+    pub fn show(&self, id: iced::window::Id) -> Message {
+        let data = self.data.clone();
+        if let Some(callback) = &self.callback_closed {
+            Message::show_dialog(id, data, *callback)
+        } else {
+            Message::show_dialog(id, data, |_| Message::None)
+        }
+    }
+}
+
+#[allow(unused)]
+impl<K> FileDialog<K>
+where
+    K: FileDialogKind,
+    K: FilesKind,
+{
+    /// add a file filter, the first one is selected
+    pub fn filter(mut self, filter: FileFilter) -> Self {
+        if self.data.filter.is_none() {
+            self.data.filter = Some(filter.clone());
+        }
+        self.data.filters.push(filter);
+        self
+    }
+
+    /// set the initial file name
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.data.file_name = file_name.into();
+        self
+    }
+}
+
+/// the file dialog kinds choosing files
+pub trait FilesKind {}
+impl FilesKind for OpenFile {}
+impl FilesKind for SaveFile {}
+
+#[allow(unused)]
+impl OpenFileDialog {
+    /// allow to choose multiple files, default is false
+    pub fn multiselect(mut self, multiselect: bool) -> Self {
+        self.data.multiselect = multiselect;
+        self
+    }
+}
+
+#[allow(unused)]
+impl SaveFileDialog {
+    /// confirm by a message box before overwriting the existing file, default is true
+    pub fn overwrite_prompt(mut self, overwrite_prompt: bool) -> Self {
+        self.data.overwrite_prompt = overwrite_prompt;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_matches_extensions() {
+        let filter = FileFilter::new("图片", &["PNG", "jpg"]);
+        assert!(filter.matches(Path::new("a.png")));
+        assert!(filter.matches(Path::new("dir/b.JPG")));
+        assert!(!filter.matches(Path::new("c.gif")));
+        assert!(!filter.matches(Path::new("png")));
+        assert!(FileFilter::all().matches(Path::new("no_extension")));
+        assert_eq!(filter.to_string(), "图片 (*.png;*.jpg)");
+    }
+
+    #[test]
+    fn read_entries_by_filter() {
+        let directory = std::env::temp_dir().join(format!("iced_kim_files_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(directory.join("folder")).unwrap();
+        for name in ["b.txt", "a.png", ".hidden.txt"] {
+            std::fs::write(directory.join(name), name).unwrap();
+        }
+        let names = |mode, filter: Option<&FileFilter>, show_hidden| -> Vec<String> {
+            read_entries(&directory, mode, filter, show_hidden)
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.name)
+                .collect()
+        };
+        let text = FileFilter::new("文本", &["txt"]);
+        assert_eq!(
+            names(FileDialogMode::Open, Some(&text), false),
+            ["folder", "b.txt"]
+        );
+        assert_eq!(
+            names(FileDialogMode::Open, None, false),
+            ["folder", "a.png", "b.txt"]
+        );
+        assert_eq!(names(FileDialogMode::Folder, None, false), ["folder"]);
+        #[cfg(unix)]
+        assert_eq!(
+            names(FileDialogMode::Open, Some(&text), true),
+            ["folder", ".hidden.txt", "b.txt"]
+        );
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,451 @@
+// ! a font picker dialog like winform FontDialog
+// ! the families are listed from the font database of iced, including the fonts loaded by the app
+// ! get the chosen font in the close callback by `get_dialog_data::<FontDialogData>()`
+
+use iced::advanced::graphics::text::font_system;
+use iced::font::{Family, Style, Weight};
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, slider, text, text_input,
+};
+use iced::window::Level;
+use iced::{window, Alignment, Element, Font, Length, Theme};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use super::{message_box_button, MessageBoxButton};
+use crate::core::{
+    DialogResult, EventMessage, IWindow, IWindowMessage, Message, Window, WindowCloseCallback,
+    WindowPlacement,
+};
+
+/// the font weights and their names
+const WEIGHTS: [(&str, Weight); 9] = [
+    ("极细", Weight::Thin),
+    ("特细", Weight::ExtraLight),
+    ("细体", Weight::Light),
+    ("常规", Weight::Normal),
+    ("中等", Weight::Medium),
+    ("半粗", Weight::Semibold),
+    ("粗体", Weight::Bold),
+    ("特粗", Weight::ExtraBold),
+    ("极粗", Weight::Black),
+];
+
+/// the font styles and their names
+const STYLES: [(&str, Style); 3] = [
+    ("常规", Style::Normal),
+    ("斜体", Style::Italic),
+    ("倾斜", Style::Oblique),
+];
+
+/// the range of the font size
+const SIZES: std::ops::RangeInclusive<f32> = 6.0..=72.0;
+
+/// the family names given to [Font], a name is leaked only once
+static FAMILY_NAMES: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+/// get the static family name for [Family::Name]
+fn intern(name: &str) -> &'static str {
+    let Ok(mut names) = FAMILY_NAMES.lock() else {
+        return Box::leak(name.to_string().into_boxed_str());
+    };
+    match names.get(name) {
+        Some(name) => name,
+        None => {
+            let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+            names.insert(name);
+            name
+        }
+    }
+}
+
+/// get the font families known to the text system of iced, sorted by the name
+/// * the fonts loaded by the app are included after they are loaded
+pub fn font_families() -> Vec<&'static str> {
+    let Ok(mut fonts) = font_system().write() else {
+        return vec![];
+    };
+    let families: BTreeSet<String> = fonts
+        .raw()
+        .db()
+        .faces()
+        .filter_map(|face| face.families.first().map(|(family, _)| family.clone()))
+        .collect();
+    families.iter().map(|family| intern(family)).collect()
+}
+
+/// create a new font dialog with a new window
+/// * `title` - the title of the font dialog
+pub fn font_dialog(title: impl Into<String>) -> FontDialog {
+    FontDialog::new(title)
+}
+
+/// the font dialog message, the window id is the font dialog window
+#[derive(Debug, Clone)]
+pub enum FontDialogMessage {
+    Search(window::Id, String),
+    Family(window::Id, &'static str),
+    Weight(window::Id, Weight),
+    Style(window::Id, Style),
+    /// the size input
+    SizeInput(window::Id, String),
+    Size(window::Id, f32),
+}
+
+impl IWindowMessage for FontDialogMessage {}
+
+/// ! the font dialog window data
+#[derive(Debug, Clone)]
+pub struct FontDialogData {
+    pub title: String,
+    /// ! the chosen font when the dialog is closed with `DialogResult::Ok`
+    pub font: Font,
+    /// ! the chosen font size
+    pub size: f32,
+    /// the text to preview the font
+    pub preview: String,
+    pub ok: MessageBoxButton,
+    pub cancel: MessageBoxButton,
+    pub window_settings: iced::window::Settings,
+    families: Vec<&'static str>,
+    search: String,
+    size_input: String,
+}
+
+impl Default for FontDialogData {
+    fn default() -> Self {
+        Self {
+            title: "字体".into(),
+            font: Font::DEFAULT,
+            size: 16.0,
+            preview: "AaBbYyZz 字体预览".into(),
+            ok: message_box_button("确定").dialog_result(DialogResult::Ok),
+            cancel: message_box_button("取消").dialog_result(DialogResult::Cancel),
+            window_settings: iced::window::Settings {
+                min_size: Some(iced::Size::new(560.0, 420.0)),
+                size: iced::Size::new(560.0, 420.0),
+                position: iced::window::Position::Centered,
+                level: Level::AlwaysOnTop,
+                resizable: true,
+                icon: None,
+                ..Default::default()
+            },
+            families: Vec::new(),
+            search: String::new(),
+            size_input: "16".into(),
+        }
+    }
+}
+
+impl FontDialogData {
+    /// the name of the chosen family
+    pub fn family(&self) -> Option<&'static str> {
+        match self.font.family {
+            Family::Name(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn set_size(&mut self, size: f32) {
+        // ! NaN passes through the clamp
+        if !size.is_finite() {
+            return;
+        }
+        self.size = size.clamp(*SIZES.start(), *SIZES.end());
+    }
+}
+
+impl IWindow for FontDialogData {
+    fn new_window(&self) -> Window {
+        Window::builder(self.to_owned())
+            .title(self.title.clone())
+            .settings(self.window_settings.clone())
+            .placement(WindowPlacement::CenteredOnParent)
+            .build()
+    }
+
+    fn update(
+        &mut self,
+        id: &window::Id,
+        message: &std::boxed::Box<dyn IWindowMessage>,
+    ) -> Message {
+        let Some(msg) = message.downcast_ref::<FontDialogMessage>() else {
+            return Message::None;
+        };
+        match msg.clone() {
+            FontDialogMessage::Search(dialog_id, search) if dialog_id == *id => {
+                self.search = search;
+            }
+            FontDialogMessage::Family(dialog_id, family) if dialog_id == *id => {
+                self.font.family = Family::Name(family);
+            }
+            FontDialogMessage::Weight(dialog_id, weight) if dialog_id == *id => {
+                self.font.weight = weight;
+            }
+            FontDialogMessage::Style(dialog_id, style) if dialog_id == *id => {
+                self.font.style = style;
+            }
+            // * the size input accepts the incomplete number
+            FontDialogMessage::SizeInput(dialog_id, value)
+                if dialog_id == *id
+                    && (value.is_empty() || value.parse::<f32>().is_ok_and(f32::is_finite)) =>
+            {
+                if let Ok(size) = value.parse::<f32>() {
+                    self.set_size(size);
+                }
+                self.size_input = value;
+            }
+            FontDialogMessage::Size(dialog_id, size) if dialog_id == *id => {
+                self.set_size(size);
+                self.size_input = format!("{}", self.size);
+            }
+            _ => {}
+        }
+        Message::None
+    }
+
+    fn on_close_request(&self, id: window::Id) -> Message {
+        EventMessage::Close(id, self.cancel.message.clone()).into()
+    }
+
+    fn view(&self, _window: &Window, id: window::Id) -> Element<'_, Message> {
+        let search = self.search.to_lowercase();
+        let chosen = self.family();
+        let families = column(
+            self.families
+                .iter()
+                .filter(|family| family.to_lowercase().contains(&search))
+                .map(|family| {
+                    button(text(*family))
+                        .width(Length::Fill)
+                        .on_press(FontDialogMessage::Family(id, family).into())
+                        .style(match chosen == Some(family) {
+                            true => button::primary,
+                            false => button::text,
+                        })
+                        .into()
+                }),
+        );
+        let family_list = column![
+            text_input("搜索字体", &self.search)
+                .on_input(move |search| FontDialogMessage::Search(id, search).into()),
+            container(scrollable(families).height(Length::Fill))
+                .height(Length::Fill)
+                .style(container::bordered_box),
+        ]
+        .spacing(10)
+        .width(Length::FillPortion(1));
+
+        let weight_names: Vec<&'static str> = WEIGHTS.iter().map(|(name, _)| *name).collect();
+        let weight = WEIGHTS
+            .iter()
+            .find(|(_, weight)| *weight == self.font.weight)
+            .map(|(name, _)| *name);
+        let style_names: Vec<&'static str> = STYLES.iter().map(|(name, _)| *name).collect();
+        let style = STYLES
+            .iter()
+            .find(|(_, style)| *style == self.font.style)
+            .map(|(name, _)| *name);
+
+        let options = column![
+            row![
+                text("粗细").width(40),
+                pick_list(weight_names, weight, move |name| {
+                    let weight = WEIGHTS
+                        .iter()
+                        .find(|(weight_name, _)| *weight_name == name)
+                        .map_or(Weight::Normal, |(_, weight)| *weight);
+                    FontDialogMessage::Weight(id, weight).into()
+                })
+                .width(Length::Fill),
+            ]
+            .spacing(5)
+            .align_y(Alignment::Center),
+            row![
+                text("样式").width(40),
+                pick_list(style_names, style, move |name| {
+                    let style = STYLES
+                        .iter()
+                        .find(|(style_name, _)| *style_name == name)
+                        .map_or(Style::Normal, |(_, style)| *style);
+                    FontDialogMessage::Style(id, style).into()
+                })
+                .width(Length::Fill),
+            ]
+            .spacing(5)
+            .align_y(Alignment::Center),
+            row![
+                text("大小").width(40),
+                text_input("", &self.size_input)
+                    .on_input(move |value| FontDialogMessage::SizeInput(id, value).into()),
+            ]
+            .spacing(5)
+            .align_y(Alignment::Center),
+            slider(SIZES, self.size, move |size| {
+                FontDialogMessage::Size(id, size).into()
+            })
+            .step(1.0),
+        ]
+        .spacing(10);
+
+        let preview = container(
+            text(&self.preview)
+                .font(self.font)
+                .size(self.size)
+                .wrapping(text::Wrapping::WordOrGlyph),
+        )
+        .center(Length::Fill)
+        .padding(5)
+        .style(|theme: &Theme| container::bordered_box(theme));
+
+        let settings = column![options, text("预览"), preview]
+            .spacing(10)
+            .width(Length::FillPortion(1));
+
+        let button_ok = self
+            .ok
+            .to_button()
+            .on_press(EventMessage::Close(id, self.ok.message.clone()).into())
+            .style(button::primary);
+
+        let button_cancel = self
+            .cancel
+            .to_button()
+            .on_press(EventMessage::Close(id, self.cancel.message.clone()).into())
+            .style(button::secondary);
+
+        let button_group = row![button_ok, button_cancel]
+            .align_y(Alignment::Center)
+            .spacing(20);
+
+        let button_layout = column![button_group]
+            .align_x(Alignment::End)
+            .width(Length::Fill);
+
+        column![
+            row![family_list, settings].spacing(15).height(Length::Fill),
+            button_layout
+        ]
+        .spacing(10)
+        .padding(10)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
+
+/// ! the font dialog builder
+pub struct FontDialog {
+    data: FontDialogData,
+    callback_closed: Option<WindowCloseCallback>,
+}
+
+#[allow(unused)]
+impl FontDialog {
+    /// create a new font dialog with the title
+    /// * `title` - the title of the dialog window
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            data: FontDialogData {
+                title: title.into(),
+                ..Default::default()
+            },
+            callback_closed: None,
+        }
+    }
+
+    /// set the initial font
+    pub fn font(mut self, font: Font) -> Self {
+        self.data.font = font;
+        self
+    }
+
+    /// set the initial font by the family name, e.g. "微软雅黑"
+    pub fn family(mut self, family: &str) -> Self {
+        self.data.font.family = Family::Name(intern(family));
+        self
+    }
+
+    /// set the initial font size
+    pub fn font_size(mut self, size: f32) -> Self {
+        self.data.set_size(size);
+        self.data.size_input = format!("{}", self.data.size);
+        self
+    }
+
+    /// set the text to preview the font
+    pub fn preview(mut self, preview: impl Into<String>) -> Self {
+        self.data.preview = preview.into();
+        self
+    }
+
+    /// set the size of the dialog
+    pub fn size(mut self, size: iced::Size) -> Self {
+        self.data.window_settings.size = size;
+        self
+    }
+
+    /// set the ok button, the dialog result should be `DialogResult::Ok`
+    pub fn ok_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.ok = button;
+        self
+    }
+
+    /// set the cancel button, the dialog result should be `DialogResult::Cancel`
+    pub fn cancel_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.cancel = button;
+        self
+    }
+
+    /// set the callback function when the dialog is closed
+    /// * get the chosen font and size by `get_dialog_data::<FontDialogData>()`
+    pub fn on_closed(mut self, callback: WindowCloseCallback) -> Self {
+        self.callback_closed = Some(callback);
+        self
+    }
+
+    /// show the font dialog window
+    /// * `id` - current window id, this will be a parent id for the new window
+    pub fn show(&self, id: iced::window::Id) -> Message {
+        let data = FontDialogData {
+            families: font_families(),
+            ..self.data.clone()
+        };
+        if let Some(callback) = &self.callback_closed {
+            Message::show_dialog(id, data, *callback)
+        } else {
+            Message::show_dialog(id, data, |_| Message::None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(data: &mut FontDialogData, id: window::Id, value: &str) {
+        let message: Box<dyn IWindowMessage> =
+            Box::new(FontDialogMessage::SizeInput(id, value.into()));
+        let _ = data.update(&id, &message);
+    }
+
+    #[test]
+    fn size_input_rejects_non_finite() {
+        let id = window::Id::unique();
+        let mut data = FontDialogData::default();
+        input(&mut data, id, "20");
+        for value in ["NaN", "inf", "-inf"] {
+            input(&mut data, id, value);
+            assert_eq!(data.size, 20.0);
+            assert_eq!(data.size_input, "20");
+        }
+        input(&mut data, id, "100");
+        assert_eq!(data.size, *SIZES.end());
+    }
+
+    #[test]
+    fn font_size_ignores_nan() {
+        let dialog = font_dialog("字体").font_size(f32::NAN);
+        assert_eq!(dialog.data.size, FontDialogData::default().size);
+    }
+}
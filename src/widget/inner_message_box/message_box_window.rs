@@ -2,22 +2,85 @@
 
 use std::fmt::Debug;
 
-use iced::widget::{button, column, row, text};
+use iced::keyboard::key::Named;
+use iced::keyboard::{Key, Modifiers};
+use iced::widget::{button, column, container, row, text};
 use iced::window::Level;
-use iced::{window, Alignment, Element, Length};
+use iced::{font, window, Alignment, Color, Element, Font, Length, Theme};
 
-use crate::core::{DialogResult, EventMessage, IWindow, Message, Window};
+use crate::core::{DialogResult, EventMessage, IWindow, Message, Window, WindowPlacement};
+use crate::widget::CharIcon;
 
 use super::{message_box_button, MessageBoxButton};
 
+/// the severity icon of the message box
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessageBoxIcon {
+    #[default]
+    None,
+    Info,
+    Warning,
+    Error,
+    Question,
+}
+
+impl MessageBoxIcon {
+    /// the char drawn in the icon
+    fn char(self) -> Option<char> {
+        match self {
+            MessageBoxIcon::None => None,
+            MessageBoxIcon::Info => Some('i'),
+            MessageBoxIcon::Warning => Some('!'),
+            MessageBoxIcon::Error => Some('×'),
+            MessageBoxIcon::Question => Some('?'),
+        }
+    }
+
+    /// the accent color of the severity
+    pub fn color(self, theme: &Theme) -> Color {
+        let palette = theme.extended_palette();
+        match self {
+            MessageBoxIcon::None | MessageBoxIcon::Info | MessageBoxIcon::Question => {
+                palette.primary.base.color
+            }
+            // * the palette has no warning color
+            MessageBoxIcon::Warning => Color::from_rgb8(0xF5, 0x9E, 0x0B),
+            MessageBoxIcon::Error => palette.danger.base.color,
+        }
+    }
+
+    /// the round icon filled with the accent color
+    fn view<'a>(self) -> Option<Element<'a, Message>> {
+        let icon = CharIcon::new(self.char()?).font(Font {
+            weight: font::Weight::Bold,
+            ..Font::DEFAULT
+        });
+        Some(
+            container(icon.small().color(Color::WHITE))
+                .center(36)
+                .style(move |theme: &Theme| {
+                    container::background(self.color(theme)).border(iced::border::rounded(18))
+                })
+                .into(),
+        )
+    }
+}
+
 /// dialog config
 #[allow(unused)]
 #[derive(Debug, Clone)]
 pub struct Data {
     pub title: String,
     pub msg: String,
-    pub primary: MessageBoxButton,
-    pub secondary: MessageBoxButton,
+    pub icon: MessageBoxIcon,
+    /// the buttons from left to right
+    pub buttons: Vec<MessageBoxButton>,
+    /// the alignment of the buttons, default is the end
+    pub button_align: Alignment,
+    /// the index of the default button, it's highlighted and pressed by Enter
+    pub default_button: Option<usize>,
+    /// the index of the cancel button, it's pressed by Escape or closing the window
+    pub cancel_button: Option<usize>,
     pub window_settings: iced::window::Settings,
 }
 
@@ -26,8 +89,14 @@ impl Default for Data {
         Self {
             title: "确认窗口".into(),
             msg: "确定关闭窗口?".into(),
-            primary: message_box_button("确定").dialog_result(DialogResult::Ok),
-            secondary: message_box_button("取消").dialog_result(DialogResult::Cancel),
+            icon: MessageBoxIcon::None,
+            buttons: vec![
+                message_box_button("确定").dialog_result(DialogResult::Ok),
+                message_box_button("取消").dialog_result(DialogResult::Cancel),
+            ],
+            button_align: Alignment::End,
+            default_button: Some(0),
+            cancel_button: Some(1),
             window_settings: iced::window::Settings {
                 min_size: Some(iced::Size::new(400.0, 200.0)),
                 size: iced::Size::new(400.0, 200.0),
@@ -41,53 +110,69 @@ impl Default for Data {
     }
 }
 
+impl Data {
+    /// the message to close the dialog by the visible button
+    /// * `index` - the index of the button
+    fn press(&self, id: window::Id, index: Option<usize>) -> Option<Message> {
+        let button = self.buttons.get(index?).filter(|button| button.visible)?;
+        Some(EventMessage::Close(id, button.message.clone()).into())
+    }
+}
+
 impl IWindow for Data {
     fn new_window(&self) -> Window {
-        Window {
-            title: "确认窗口".into(),
-            data: Box::new(self.to_owned()),
-            settings: self.window_settings.clone(),
-            ..Default::default()
-        }
+        Window::builder(self.to_owned())
+            .title(self.title.clone())
+            .settings(self.window_settings.clone())
+            .placement(WindowPlacement::CenteredOnParent)
+            .build()
     }
 
-    fn view(&self, _window: &Window, id: window::Id) -> Element<Message> {
-        let button_primary = self
-            .primary
-            .to_button()
-            .on_press(EventMessage::Close(id, self.primary.message.clone()).into())
-            .style(button::primary);
-
-        let button_secondary = self
-            .secondary
-            .to_button()
-            .on_press(EventMessage::Close(id, self.secondary.message.clone()).into())
-            .style(button::secondary);
+    fn on_close_request(&self, id: window::Id) -> Message {
+        self.press(id, self.cancel_button)
+            .unwrap_or_else(|| EventMessage::Close(id, DialogResult::None).into())
+    }
 
+    fn on_key_pressed(&self, id: window::Id, key: Key, _modifiers: Modifiers) -> Message {
+        let index = match key {
+            Key::Named(Named::Enter) => self.default_button,
+            Key::Named(Named::Escape) => self.cancel_button,
+            _ => None,
+        };
+        self.press(id, index).unwrap_or(Message::None)
+    }
+
+    fn view(&self, _window: &Window, id: window::Id) -> Element<Message> {
         let msg = text(self.msg.to_string()).height(Length::Fill);
 
-        let content = row![msg]
+        let content = row![]
+            .push_maybe(self.icon.view())
+            .push(msg)
+            .spacing(15)
             .width(Length::Fill)
             .height(Length::Fill)
             .padding(10);
 
-        let button_group = row![]
-            .push_maybe(if self.primary.visible {
-                Some(button_primary)
-            } else {
-                None
-            })
-            .push_maybe(if self.secondary.visible {
-                Some(button_secondary)
-            } else {
-                None
-            })
-            .align_y(Alignment::Center)
-            .spacing(20)
-            .padding(10);
+        let button_group = row(self
+            .buttons
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.visible)
+            .map(|(index, item)| {
+                item.to_button()
+                    .on_press(EventMessage::Close(id, item.message.clone()).into())
+                    .style(match self.default_button == Some(index) {
+                        true => button::primary,
+                        false => button::secondary,
+                    })
+                    .into()
+            }))
+        .align_y(Alignment::Center)
+        .spacing(20)
+        .padding(10);
 
         let button_layout = column![button_group]
-            .align_x(Alignment::End)
+            .align_x(self.button_align)
             .width(Length::Fill);
 
         let group = column![content, button_layout]
@@ -1,7 +1,7 @@
 mod message_box_button;
 mod message_box_window;
 pub use message_box_button::MessageBoxButton;
-pub use message_box_window::Data as MessageBoxData;
+pub use message_box_window::{Data as MessageBoxData, MessageBoxIcon};
 
 pub fn message_box_button(text: impl Into<String>) -> MessageBoxButton {
     MessageBoxButton::new(text)
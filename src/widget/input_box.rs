@@ -0,0 +1,434 @@
+// ! an input dialog like winform InputBox
+// ! get the entered value in the close callback by `get_dialog_data::<InputBoxData>()`
+
+use iced::widget::{button, column, row, text, text_editor, text_input};
+use iced::window::Level;
+use iced::{window, Alignment, Element, Length};
+use std::sync::Arc;
+
+use super::{message_box_button, MessageBoxButton};
+use crate::core::{
+    DialogResult, EventMessage, IWindow, IWindowMessage, Message, Window, WindowCloseCallback,
+    WindowPlacement,
+};
+
+/// create a new input box with a new window
+/// * `title` - the title of the input box
+/// * `prompt` - the prompt above the input
+pub fn input_box(title: impl Into<String>, prompt: impl Into<String>) -> InputBox {
+    InputBox::new(title, prompt)
+}
+
+/// the validation of the entered value
+/// * `Err(reason)` - the reason shown to the user, the ok button is disabled
+pub type InputValidator = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// the kind of the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputKind {
+    #[default]
+    Text,
+    /// the entered text is masked
+    Password,
+    /// a multiline text editor
+    Multiline,
+    /// only a number can be entered
+    Numeric,
+}
+
+/// the input box message, the window id is the input box window
+#[derive(Debug, Clone)]
+pub enum InputBoxMessage {
+    Changed(window::Id, String),
+    Edited(window::Id, text_editor::Action),
+}
+
+impl IWindowMessage for InputBoxMessage {}
+
+/// ! the input box window data
+pub struct InputBoxData {
+    pub title: String,
+    pub prompt: String,
+    /// the entered value
+    pub value: String,
+    pub kind: InputKind,
+    pub placeholder: String,
+    pub validator: Option<InputValidator>,
+    pub ok: MessageBoxButton,
+    pub cancel: MessageBoxButton,
+    pub window_settings: iced::window::Settings,
+    /// the content of the multiline editor
+    content: text_editor::Content,
+}
+
+impl Clone for InputBoxData {
+    fn clone(&self) -> Self {
+        Self {
+            title: self.title.clone(),
+            prompt: self.prompt.clone(),
+            value: self.value.clone(),
+            kind: self.kind,
+            placeholder: self.placeholder.clone(),
+            validator: self.validator.clone(),
+            ok: self.ok.clone(),
+            cancel: self.cancel.clone(),
+            window_settings: self.window_settings.clone(),
+            // ! the editor content can't be cloned, rebuild it by the text
+            content: text_editor::Content::with_text(&self.value),
+        }
+    }
+}
+
+impl std::fmt::Debug for InputBoxData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputBoxData")
+            .field("title", &self.title)
+            .field("prompt", &self.prompt)
+            .field("value", &self.value)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+
+impl Default for InputBoxData {
+    fn default() -> Self {
+        Self {
+            title: "输入".into(),
+            prompt: String::new(),
+            value: String::new(),
+            kind: InputKind::Text,
+            placeholder: String::new(),
+            validator: None,
+            ok: message_box_button("确定").dialog_result(DialogResult::Ok),
+            cancel: message_box_button("取消").dialog_result(DialogResult::Cancel),
+            window_settings: iced::window::Settings {
+                min_size: Some(iced::Size::new(400.0, 200.0)),
+                size: iced::Size::new(400.0, 200.0),
+                position: iced::window::Position::Centered,
+                level: Level::AlwaysOnTop,
+                resizable: true,
+                icon: None,
+                ..Default::default()
+            },
+            content: text_editor::Content::new(),
+        }
+    }
+}
+
+impl InputBoxData {
+    /// validate the entered value
+    /// * the numeric input must be a complete finite number, e.g. not "1e" or "-"
+    pub fn validate(&self) -> Result<(), String> {
+        if self.kind == InputKind::Numeric && self.number().is_none() {
+            return Err("请输入数字".into());
+        }
+        match &self.validator {
+            Some(validator) => validator(&self.value),
+            None => Ok(()),
+        }
+    }
+
+    /// the entered value as a number, only for `InputKind::Numeric`
+    pub fn number(&self) -> Option<f64> {
+        self.value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite())
+    }
+
+    /// whether the value can be entered in the numeric input
+    /// * the value is typed char by char, so a prefix of a number is accepted, e.g. "-.", "1." or "1e-"
+    /// * the complete number is checked by [InputBoxData::validate]
+    fn is_numeric(value: &str) -> bool {
+        let value = value.strip_prefix(['+', '-']).unwrap_or(value);
+        let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+            None => (value, None),
+        };
+        let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if !digits(integer) || !digits(fraction) {
+            return false;
+        }
+        match exponent {
+            // ! the exponent needs the digits before it
+            Some(exponent) => {
+                !(integer.is_empty() && fraction.is_empty())
+                    && digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent))
+            }
+            None => true,
+        }
+    }
+}
+
+impl IWindow for InputBoxData {
+    fn new_window(&self) -> Window {
+        Window::builder(self.to_owned())
+            .title(self.title.clone())
+            .settings(self.window_settings.clone())
+            .placement(WindowPlacement::CenteredOnParent)
+            .build()
+    }
+
+    fn update(
+        &mut self,
+        id: &window::Id,
+        message: &std::boxed::Box<dyn IWindowMessage>,
+    ) -> Message {
+        match message.downcast_ref::<InputBoxMessage>() {
+            // * the numeric input ignores the invalid value
+            Some(InputBoxMessage::Changed(input_id, value))
+                if input_id == id
+                    && (self.kind != InputKind::Numeric || Self::is_numeric(value)) =>
+            {
+                self.value = value.clone();
+            }
+            Some(InputBoxMessage::Edited(input_id, action)) if input_id == id => {
+                self.content.perform(action.clone());
+                self.value = self.content.text();
+            }
+            _ => {}
+        }
+        Message::None
+    }
+
+    fn on_close_request(&self, id: window::Id) -> Message {
+        EventMessage::Close(id, self.cancel.message.clone()).into()
+    }
+
+    fn view(&self, _window: &Window, id: window::Id) -> Element<'_, Message> {
+        let validation = self.validate();
+        let submit = validation
+            .is_ok()
+            .then(|| EventMessage::Close(id, self.ok.message.clone()).into());
+
+        let input: Element<'_, Message> = match self.kind {
+            InputKind::Multiline => text_editor(&self.content)
+                .placeholder(self.placeholder.as_str())
+                .on_action(move |action| InputBoxMessage::Edited(id, action).into())
+                .height(Length::Fill)
+                .into(),
+            kind => text_input(&self.placeholder, &self.value)
+                .secure(kind == InputKind::Password)
+                .on_input(move |value| InputBoxMessage::Changed(id, value).into())
+                .on_submit_maybe(submit.clone())
+                .into(),
+        };
+
+        let error = validation.err().map(|reason| {
+            text(reason).style(|theme: &iced::Theme| text::Style {
+                color: Some(theme.extended_palette().danger.base.color),
+            })
+        });
+
+        let button_ok = self
+            .ok
+            .to_button()
+            .on_press_maybe(submit)
+            .style(button::primary);
+
+        let button_cancel = self
+            .cancel
+            .to_button()
+            .on_press(EventMessage::Close(id, self.cancel.message.clone()).into())
+            .style(button::secondary);
+
+        let button_group = row![]
+            .push_maybe(self.ok.visible.then_some(button_ok))
+            .push_maybe(self.cancel.visible.then_some(button_cancel))
+            .align_y(Alignment::Center)
+            .spacing(20);
+
+        let button_layout = column![button_group]
+            .align_x(Alignment::End)
+            .width(Length::Fill);
+
+        let content = column![text(&self.prompt), input]
+            .push_maybe(error)
+            .spacing(10)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        column![content, button_layout]
+            .spacing(20)
+            .padding(10)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
+
+/// ! the input box builder
+pub struct InputBox {
+    data: InputBoxData,
+    callback_closed: Option<WindowCloseCallback>,
+}
+
+#[allow(unused)]
+impl InputBox {
+    /// create a new input box with the title and prompt
+    /// * `title` - the title of the dialog
+    /// * `prompt` - the prompt above the input
+    pub fn new(title: impl Into<String>, prompt: impl Into<String>) -> Self {
+        Self {
+            data: InputBoxData {
+                title: title.into(),
+                prompt: prompt.into(),
+                ..Default::default()
+            },
+            callback_closed: None,
+        }
+    }
+
+    /// set the initial value
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.data.value = value.into();
+        self.data.content = text_editor::Content::with_text(&self.data.value);
+        self
+    }
+
+    /// set the placeholder of the input
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.data.placeholder = placeholder.into();
+        self
+    }
+
+    /// mask the entered text
+    pub fn password(mut self) -> Self {
+        self.data.kind = InputKind::Password;
+        self
+    }
+
+    /// use a multiline text editor
+    pub fn multiline(mut self) -> Self {
+        self.data.kind = InputKind::Multiline;
+        self
+    }
+
+    /// only a number can be entered, get it by [InputBoxData::number]
+    pub fn numeric(mut self) -> Self {
+        self.data.kind = InputKind::Numeric;
+        self
+    }
+
+    /// validate the entered value, the ok button is disabled when it returns an error
+    /// * `validator` - returns `Err(reason)` for the invalid value
+    pub fn validate(
+        mut self,
+        validator: impl Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.data.validator = Some(Arc::new(validator));
+        self
+    }
+
+    /// set the size of the dialog
+    pub fn size(mut self, size: iced::Size) -> Self {
+        self.data.window_settings.size = size;
+        self
+    }
+
+    /// set the ok button, the dialog result should be `DialogResult::Ok`
+    pub fn ok_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.ok = button;
+        self
+    }
+
+    /// set the cancel button, the dialog result should be `DialogResult::Cancel`
+    pub fn cancel_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.cancel = button;
+        self
+    }
+
+    /// set the callback function when the dialog is closed
+    /// * get the entered value by `get_dialog_data::<InputBoxData>()`
+    pub fn on_closed(mut self, callback: WindowCloseCallback) -> Self {
+        self.callback_closed = Some(callback);
+        self
+    }
+
+    /// show the input box window
+    /// * `id` - current window id, this will be a parent id for the new window
+    pub fn show(&self, id: iced::window::Id) -> Message {
+        if let Some(callback) = &self.callback_closed {
+            Message::show_dialog(id, self.data.clone(), *callback)
+        } else {
+            Message::show_dialog(id, self.data.clone(), |_| Message::None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_values() {
+        for value in [
+            "", "-", ".", "-.", "0", "-12", "1.", "3.5", ".5", "1e", "1e-", "1e3", "1.5E+2",
+        ] {
+            assert!(InputBoxData::is_numeric(value), "{:?}", value);
+        }
+        for value in [
+            "a", "1a", "--1", "1.2.3", " 1", "inf", "NaN", "e", ".e", "1e1e", "1e.5",
+        ] {
+            assert!(!InputBoxData::is_numeric(value), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn numeric_input_ignores_invalid_value() {
+        let id = window::Id::unique();
+        let mut data = InputBoxData {
+            kind: InputKind::Numeric,
+            ..Default::default()
+        };
+        let change = |data: &mut InputBoxData, value: &str| {
+            let message: Box<dyn IWindowMessage> =
+                Box::new(InputBoxMessage::Changed(id, value.into()));
+            let _ = data.update(&id, &message);
+        };
+
+        change(&mut data, "-1.5");
+        change(&mut data, "-1.5x");
+        assert_eq!(data.value, "-1.5");
+        assert_eq!(data.number(), Some(-1.5));
+
+        data.kind = InputKind::Text;
+        change(&mut data, "abc");
+        assert_eq!(data.value, "abc");
+    }
+
+    #[test]
+    fn type_numbers_char_by_char() {
+        let id = window::Id::unique();
+        for number in [".5", "-.5", "1e3", "2.5e-3"] {
+            let mut data = InputBoxData {
+                kind: InputKind::Numeric,
+                ..Default::default()
+            };
+            for end in 1..=number.len() {
+                let message: Box<dyn IWindowMessage> =
+                    Box::new(InputBoxMessage::Changed(id, number[..end].into()));
+                let _ = data.update(&id, &message);
+            }
+            assert_eq!(data.value, number);
+            assert_eq!(data.number(), number.parse().ok());
+            assert!(data.validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn validate_complete_number() {
+        let mut data = InputBoxData {
+            kind: InputKind::Numeric,
+            ..Default::default()
+        };
+        for value in ["", "-", ".", "1e", "1e400"] {
+            data.value = value.into();
+            assert!(data.validate().is_err(), "{:?}", value);
+        }
+        data.value = "1.".into();
+        assert!(data.validate().is_ok());
+    }
+}
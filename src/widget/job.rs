@@ -0,0 +1,432 @@
+// ! background jobs owned by windows
+// ! the progress and the result of a job are routed only to its owner window
+// ! the jobs are cancelled when the owner window is closed
+// ! the running jobs are registered in the program, read them by `Window::running_jobs`
+
+use iced::futures::channel::oneshot;
+use iced::futures::future::BoxFuture;
+use iced::futures::FutureExt;
+use iced::{window, Task};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use super::program_handle::ProgramHandle;
+use crate::core::{IWindowMessage, Message, WindowMessage};
+
+/// the next job id
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// the unique id of a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JobId(u64);
+
+/// the information of a running job
+#[derive(Debug, Clone)]
+pub struct JobInfo {
+    pub id: JobId,
+    pub name: String,
+    /// the window to receive the progress and the result
+    pub owner: window::Id,
+    pub started: Instant,
+    /// the job is cancelled, but it's still running until it checks [JobContext::is_cancelled]
+    pub cancelled: bool,
+}
+
+/// a running job in the registry
+struct RunningJob {
+    info: JobInfo,
+    cancelled: Arc<AtomicBool>,
+    /// only the async job can be aborted
+    abort: Option<iced::task::Handle>,
+}
+
+/// the context of a running job, it can be moved to any thread
+#[derive(Debug, Clone)]
+pub struct JobContext {
+    id: JobId,
+    owner: window::Id,
+    cancelled: Arc<AtomicBool>,
+    handle: ProgramHandle,
+}
+
+impl JobContext {
+    /// the job id
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+
+    /// the owner window id
+    pub fn owner(&self) -> window::Id {
+        self.owner
+    }
+
+    /// whether the job is cancelled, a long job should check it and return early
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// send a progress message only to the owner window
+    /// # Return
+    /// * `false` - the program has exited
+    pub fn progress<T>(&self, msg: T) -> bool
+    where
+        T: IWindowMessage + Send,
+    {
+        self.handle.send_to(self.owner, msg)
+    }
+}
+
+/// the work of a job
+enum JobWork {
+    /// run in a new thread
+    Thread(Box<dyn FnOnce(JobContext) + Send>),
+    /// run in the executor of iced, it can be aborted
+    Future(Box<dyn FnOnce(JobContext) -> BoxFuture<'static, Message> + Send>),
+}
+
+/// the job to start, see [Job]
+#[derive(Clone)]
+pub struct JobData {
+    id: JobId,
+    name: String,
+    owner: window::Id,
+    /// the work is taken once when the job starts
+    work: Arc<Mutex<Option<JobWork>>>,
+}
+
+impl std::fmt::Debug for JobData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobData")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("owner", &self.owner)
+            .finish()
+    }
+}
+
+/// the job message, it's handled by the program
+#[derive(Debug, Clone)]
+pub enum JobMessage {
+    /// start a job, see [Job::start]
+    Start(JobData),
+    /// cancel a job, see [Job::cancel]
+    Cancel(JobId),
+    /// the job is finished with the result, it's sent by the job
+    Finished(JobId, WindowMessage),
+    /// the job panicked, it's removed without the result
+    Panicked(JobId),
+    /// a background work of the framework, it's not registered as a job
+    Background(BackgroundWork),
+}
+
+impl From<JobMessage> for Message {
+    fn from(value: JobMessage) -> Self {
+        Message::Job(value)
+    }
+}
+
+/// ! a background job owned by a window
+/// * `Job::thread(id, "import", |ctx| ...).start()` - create the message to start it
+/// * the result of the work is sent only to the owner window when the job is finished
+pub struct Job {
+    data: JobData,
+}
+
+impl Job {
+    /// a job running in a new thread
+    /// # Arguments
+    /// * `owner` - the window to receive the progress and the result
+    /// * `name` - the job name shown by [crate::core::Window::running_jobs]
+    /// * `work` - the work, it returns the result message for the owner
+    pub fn thread<F, T>(owner: window::Id, name: impl Into<String>, work: F) -> Self
+    where
+        F: FnOnce(JobContext) -> T + Send + 'static,
+        T: IWindowMessage + Send,
+    {
+        let work = JobWork::Thread(Box::new(move |context: JobContext| {
+            let id = context.id;
+            let handle = context.handle.clone();
+            // ! the job must be removed even if the work panics
+            let message = match std::panic::catch_unwind(AssertUnwindSafe(|| work(context))) {
+                Ok(result) => JobMessage::Finished(id, WindowMessage::new(result)),
+                Err(_) => JobMessage::Panicked(id),
+            };
+            handle.send(message.into());
+        }));
+        Self::new(owner, name.into(), work)
+    }
+
+    /// an async job running in the executor of iced
+    /// # Arguments
+    /// * `owner` - the window to receive the progress and the result
+    /// * `name` - the job name shown by [crate::core::Window::running_jobs]
+    /// * `work` - the work, its future returns the result message for the owner
+    pub fn future<F, Fut, T>(owner: window::Id, name: impl Into<String>, work: F) -> Self
+    where
+        F: FnOnce(JobContext) -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+        T: IWindowMessage + Send,
+    {
+        let work = JobWork::Future(Box::new(move |context: JobContext| {
+            let id = context.id;
+            AssertUnwindSafe(work(context))
+                .catch_unwind()
+                .map(move |result| match result {
+                    Ok(result) => JobMessage::Finished(id, WindowMessage::new(result)).into(),
+                    Err(_) => JobMessage::Panicked(id).into(),
+                })
+                .boxed()
+        }));
+        Self::new(owner, name.into(), work)
+    }
+
+    fn new(owner: window::Id, name: String, work: JobWork) -> Self {
+        Self {
+            data: JobData {
+                id: JobId(NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+                name,
+                owner,
+                work: Arc::new(Mutex::new(Some(work))),
+            },
+        }
+    }
+
+    /// the job id, keep it to cancel the job
+    pub fn id(&self) -> JobId {
+        self.data.id
+    }
+
+    /// the message to start the job
+    pub fn start(self) -> Message {
+        JobMessage::Start(self.data).into()
+    }
+
+    /// the message to cancel the job
+    pub fn cancel(id: JobId) -> Message {
+        JobMessage::Cancel(id).into()
+    }
+}
+
+/// the work of a [BackgroundWork]
+type BackgroundFn = Box<dyn FnOnce() -> WindowMessage + Send>;
+
+/// a background work of the framework, e.g. listing a directory in the file dialog
+/// * it's not listed by `Window::running_jobs` and not cancelled with the owner
+/// * the result is sent only to the owner window
+#[derive(Clone)]
+pub struct BackgroundWork {
+    owner: window::Id,
+    /// the work is taken once when it starts
+    work: Arc<Mutex<Option<BackgroundFn>>>,
+}
+
+impl std::fmt::Debug for BackgroundWork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackgroundWork")
+            .field("owner", &self.owner)
+            .finish()
+    }
+}
+
+impl BackgroundWork {
+    /// the message to run the work in a new thread
+    /// # Arguments
+    /// * `owner` - the window to receive the result
+    /// * `work` - the work, it returns the result message for the owner
+    #[cfg_attr(not(feature = "file-dialog"), allow(dead_code))]
+    pub(crate) fn start<F, T>(owner: window::Id, work: F) -> Message
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: IWindowMessage,
+    {
+        let work: BackgroundFn = Box::new(move || WindowMessage::new(work()));
+        JobMessage::Background(Self {
+            owner,
+            work: Arc::new(Mutex::new(Some(work))),
+        })
+        .into()
+    }
+
+    /// run the work, the result is dropped if the work panics
+    pub(crate) fn perform(self) -> Task<Message> {
+        let Some(work) = self.work.lock().ok().and_then(|mut work| work.take()) else {
+            return Task::none();
+        };
+        let owner = self.owner;
+        let (sender, receiver) = oneshot::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(work());
+        });
+        Task::perform(receiver, move |result| match result {
+            Ok(result) => Message::SendTo(owner, result),
+            Err(_) => Message::None,
+        })
+    }
+}
+
+/// the running jobs of a program, it's shared with the windows to read in the view
+#[derive(Clone, Default)]
+pub(crate) struct Jobs(Arc<Mutex<BTreeMap<JobId, RunningJob>>>);
+
+impl std::fmt::Debug for Jobs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.list()).finish()
+    }
+}
+
+impl Jobs {
+    /// get the running jobs
+    pub(crate) fn list(&self) -> Vec<JobInfo> {
+        self.0
+            .lock()
+            .map(|jobs| jobs.values().map(|job| job.info.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// start the job and register it
+    pub(crate) fn start(&self, data: JobData, handle: ProgramHandle) -> Task<Message> {
+        let Some(work) = data.work.lock().ok().and_then(|mut work| work.take()) else {
+            return Task::none();
+        };
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let context = JobContext {
+            id: data.id,
+            owner: data.owner,
+            cancelled: cancelled.clone(),
+            handle,
+        };
+        let info = JobInfo {
+            id: data.id,
+            name: data.name,
+            owner: data.owner,
+            started: Instant::now(),
+            cancelled: false,
+        };
+        // ! register the job before running, the thread may finish at once
+        match work {
+            JobWork::Thread(work) => {
+                self.register(info, cancelled, None);
+                std::thread::spawn(move || work(context));
+                Task::none()
+            }
+            JobWork::Future(work) => {
+                let (task, abort) = Task::perform(work(context), |message| message).abortable();
+                self.register(info, cancelled, Some(abort));
+                task
+            }
+        }
+    }
+
+    fn register(
+        &self,
+        info: JobInfo,
+        cancelled: Arc<AtomicBool>,
+        abort: Option<iced::task::Handle>,
+    ) {
+        if let Ok(mut jobs) = self.0.lock() {
+            let job = RunningJob {
+                info,
+                cancelled,
+                abort,
+            };
+            jobs.insert(job.info.id, job);
+        }
+    }
+
+    /// cancel the job, the aborted async job is removed at once
+    pub(crate) fn cancel(&self, id: JobId) {
+        let Ok(mut jobs) = self.0.lock() else {
+            return;
+        };
+        let aborted = match jobs.get_mut(&id) {
+            Some(job) => {
+                job.info.cancelled = true;
+                job.cancelled.store(true, Ordering::Relaxed);
+                job.abort.as_ref().map(|abort| abort.abort()).is_some()
+            }
+            None => false,
+        };
+        if aborted {
+            jobs.remove(&id);
+        }
+    }
+
+    /// cancel all the jobs owned by the window
+    pub(crate) fn cancel_owned(&self, owner: window::Id) {
+        let ids: Vec<JobId> = self
+            .list()
+            .into_iter()
+            .filter(|job| job.owner == owner)
+            .map(|job| job.id)
+            .collect();
+        for id in ids {
+            self.cancel(id);
+        }
+    }
+
+    /// remove the finished job and get its owner
+    pub(crate) fn finish(&self, id: JobId) -> Option<window::Id> {
+        self.0
+            .lock()
+            .ok()
+            .and_then(|mut jobs| jobs.remove(&id))
+            .map(|job| job.info.owner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::futures::StreamExt;
+
+    #[derive(Debug, Clone)]
+    struct Done;
+
+    impl IWindowMessage for Done {}
+
+    #[test]
+    fn panicked_job_is_removed() {
+        let (handle, receiver) = ProgramHandle::channel();
+        let mut receiver = receiver.lock().unwrap().take().unwrap();
+        let jobs = Jobs::default();
+        let owner = window::Id::unique();
+        let job = Job::thread(owner, "panic", |_| -> Done { panic!("job panicked") });
+        let id = job.id();
+        let Message::Job(JobMessage::Start(data)) = job.start() else {
+            unreachable!()
+        };
+        let _ = jobs.start(data, handle);
+        assert_eq!(jobs.list().len(), 1);
+        let message = iced::futures::executor::block_on(receiver.next());
+        assert!(
+            matches!(message, Some(Message::Job(JobMessage::Panicked(panicked))) if panicked == id)
+        );
+        jobs.finish(id);
+        assert!(jobs.list().is_empty());
+    }
+
+    #[test]
+    fn cancel_owned_jobs() {
+        let (handle, _receiver) = ProgramHandle::channel();
+        let jobs = Jobs::default();
+        let owner = window::Id::unique();
+        let job = Job::thread(owner, "wait", |context| {
+            while !context.is_cancelled() {
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            Done
+        });
+        let Message::Job(JobMessage::Start(data)) = job.start() else {
+            unreachable!()
+        };
+        let _ = jobs.start(data, handle);
+        jobs.cancel_owned(window::Id::unique());
+        assert!(!jobs.list()[0].cancelled);
+        jobs.cancel_owned(owner);
+        assert!(jobs.list()[0].cancelled);
+    }
+}
@@ -1,5 +1,8 @@
 // ! the message box
-use super::inner_message_box::{MessageBoxButton, MessageBoxData};
+use super::inner_message_box::{
+    message_box_button, MessageBoxButton, MessageBoxData, MessageBoxIcon,
+};
+use crate::core::DialogResult;
 use crate::core::{Message, WindowCloseCallback};
 
 /// create a new message box with a new window
@@ -12,6 +15,12 @@ pub fn message_box(title: impl Into<String>, content: impl Into<String>) -> Mess
 pub struct MessageBox {
     data: MessageBoxData,
     callback_closed: Option<WindowCloseCallback>,
+    /// the default button is set by [MessageBox::default_button], it's kept by the new buttons
+    default_set: bool,
+    /// the cancel button is set by [MessageBox::cancel_button], it's kept by the new buttons
+    cancel_set: bool,
+    /// the default ok and cancel buttons are replaced, [MessageBox::button] adds to them
+    buttons_set: bool,
 }
 
 #[allow(unused)]
@@ -27,38 +36,208 @@ impl MessageBox {
                 ..Default::default()
             },
             callback_closed: None,
+            default_set: false,
+            cancel_set: false,
+            buttons_set: false,
         }
     }
 
+    /// an information with the ok button
+    /// * `title` - the title of the dialog
+    /// * `content` - the content of the dialog
+    pub fn info(title: impl Into<String>, content: impl Into<String>) -> Self {
+        Self::new(title, content)
+            .icon(MessageBoxIcon::Info)
+            .ok_only()
+    }
+
+    /// a warning with the ok button
+    /// * `title` - the title of the dialog
+    /// * `content` - the content of the dialog
+    pub fn warning(title: impl Into<String>, content: impl Into<String>) -> Self {
+        Self::new(title, content)
+            .icon(MessageBoxIcon::Warning)
+            .ok_only()
+    }
+
+    /// an error with the ok button
+    /// * `title` - the title of the dialog
+    /// * `content` - the content of the dialog
+    pub fn error(title: impl Into<String>, content: impl Into<String>) -> Self {
+        Self::new(title, content)
+            .icon(MessageBoxIcon::Error)
+            .ok_only()
+    }
+
+    /// a question with the yes and no buttons
+    /// * `title` - the title of the dialog
+    /// * `content` - the content of the dialog
+    pub fn question(title: impl Into<String>, content: impl Into<String>) -> Self {
+        Self::new(title, content)
+            .icon(MessageBoxIcon::Question)
+            .buttons(vec![
+                message_box_button("是").dialog_result(DialogResult::Yes),
+                message_box_button("否").dialog_result(DialogResult::No),
+            ])
+            .cancel_button(1)
+    }
+
+    /// a confirmation with the ok and cancel buttons
+    /// * `title` - the title of the dialog
+    /// * `content` - the content of the dialog
+    pub fn confirm(title: impl Into<String>, content: impl Into<String>) -> Self {
+        Self::new(title, content).icon(MessageBoxIcon::Question)
+    }
+
+    /// set the severity icon
+    pub fn icon(mut self, icon: MessageBoxIcon) -> Self {
+        self.data.icon = icon;
+        self
+    }
+
     /// set the size of the dialog
     pub fn size(mut self, size: iced::Size) -> Self {
         self.data.window_settings.size = size;
         self
     }
 
-    /// set the primary button
-    /// * `button` - the primary button
-    pub fn primary_button(mut self, button: MessageBoxButton) -> Self {
-        self.data.primary = button;
+    /// set the buttons from left to right
+    /// * the first button is the default button
+    /// * the button with `DialogResult::Cancel` is the cancel button
+    /// * the default and cancel buttons set by index are kept when they are still in range
+    pub fn buttons(mut self, buttons: Vec<MessageBoxButton>) -> Self {
+        let len = buttons.len();
+        if !self.default_set || self.data.default_button.map_or(true, |index| index >= len) {
+            self.data.default_button = (len > 0).then_some(0);
+        }
+        if !self.cancel_set || self.data.cancel_button.map_or(true, |index| index >= len) {
+            self.data.cancel_button = buttons
+                .iter()
+                .position(|button| button.message == DialogResult::Cancel);
+        }
+        self.data.buttons = buttons;
+        self.buttons_set = true;
         self
     }
 
-    /// set the secondary button
-    /// * `button` - the secondary button
-    pub fn secondary_button(mut self, button: MessageBoxButton) -> Self {
-        self.data.secondary = button;
+    /// add a button at the end
+    /// * the first call replaces the default ok and cancel buttons
+    /// * the first button is the default button
+    /// * the first button with `DialogResult::Cancel` is the cancel button
+    pub fn button(mut self, button: MessageBoxButton) -> Self {
+        if !self.buttons_set {
+            self.data.buttons.clear();
+            if !self.default_set {
+                self.data.default_button = None;
+            }
+            if !self.cancel_set {
+                self.data.cancel_button = None;
+            }
+            self.buttons_set = true;
+        }
+        let index = self.data.buttons.len();
+        if self.data.default_button.is_none() {
+            self.data.default_button = Some(index);
+        }
+        if self.data.cancel_button.is_none() && button.message == DialogResult::Cancel {
+            self.data.cancel_button = Some(index);
+        }
+        self.data.buttons.push(button);
         self
     }
 
-    /// hide the primary button,
+    /// set the first button
+    /// * `button` - the primary button
+    #[deprecated(note = "use `buttons` or `button` instead")]
+    pub fn primary_button(self, button: MessageBoxButton) -> Self {
+        self.replace_button(0, button)
+    }
+
+    /// set the second button
+    /// * `button` - the secondary button
+    #[deprecated(note = "use `buttons` or `button` instead")]
+    pub fn secondary_button(self, button: MessageBoxButton) -> Self {
+        self.replace_button(1, button)
+    }
+
+    /// hide the first button
+    #[deprecated(note = "use `buttons` without the button instead")]
     pub fn hide_primary_button(mut self) -> Self {
-        self.data.primary.visible = false;
+        if let Some(button) = self.data.buttons.get_mut(0) {
+            button.visible = false;
+        }
         self
     }
 
-    /// hide the secondary button,
+    /// hide the second button
+    #[deprecated(note = "use `buttons` without the button instead")]
     pub fn hide_secondary_button(mut self) -> Self {
-        self.data.secondary.visible = false;
+        if let Some(button) = self.data.buttons.get_mut(1) {
+            button.visible = false;
+        }
+        self
+    }
+
+    /// replace the button at the index, or add it at the end
+    fn replace_button(mut self, index: usize, button: MessageBoxButton) -> Self {
+        match self.data.buttons.get_mut(index) {
+            Some(old) => {
+                *old = button;
+                self.buttons_set = true;
+                self
+            }
+            None => self.button(button),
+        }
+    }
+
+    /// only the ok button, it's also pressed by Escape
+    pub fn ok_only(self) -> Self {
+        self.buttons(vec![
+            message_box_button("确定").dialog_result(DialogResult::Ok)
+        ])
+        .cancel_button(0)
+    }
+
+    /// the save, don't save and cancel buttons
+    /// * `DialogResult::Yes` - save
+    /// * `DialogResult::No` - don't save
+    pub fn save_dont_save_cancel(self) -> Self {
+        self.buttons(vec![
+            message_box_button("保存").dialog_result(DialogResult::Yes),
+            message_box_button("不保存").dialog_result(DialogResult::No),
+            message_box_button("取消").dialog_result(DialogResult::Cancel),
+        ])
+    }
+
+    /// the abort, retry and ignore buttons, the retry button is the default button
+    pub fn abort_retry_ignore(self) -> Self {
+        self.buttons(vec![
+            message_box_button("中止").dialog_result(DialogResult::Abort),
+            message_box_button("重试").dialog_result(DialogResult::Retry),
+            message_box_button("忽略").dialog_result(DialogResult::Ignore),
+        ])
+        .default_button(1)
+    }
+
+    /// set the default button, it's highlighted and pressed by Enter
+    /// * `index` - the index of the button
+    pub fn default_button(mut self, index: usize) -> Self {
+        self.data.default_button = Some(index);
+        self.default_set = true;
+        self
+    }
+
+    /// set the cancel button, it's pressed by Escape or closing the window
+    /// * `index` - the index of the button
+    pub fn cancel_button(mut self, index: usize) -> Self {
+        self.data.cancel_button = Some(index);
+        self.cancel_set = true;
+        self
+    }
+
+    /// set the alignment of the buttons, default is the end
+    pub fn button_align(mut self, align: iced::Alignment) -> Self {
+        self.data.button_align = align;
         self
     }
 
@@ -78,3 +257,87 @@ impl MessageBox {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buttons() -> Vec<MessageBoxButton> {
+        vec![
+            message_box_button("保存").dialog_result(DialogResult::Yes),
+            message_box_button("不保存").dialog_result(DialogResult::No),
+            message_box_button("取消").dialog_result(DialogResult::Cancel),
+        ]
+    }
+
+    #[test]
+    fn buttons_keep_the_indices() {
+        let before = MessageBox::new("", "").default_button(1).buttons(buttons());
+        let after = MessageBox::new("", "").buttons(buttons()).default_button(1);
+        assert_eq!(before.data.default_button, Some(1));
+        assert_eq!(after.data.default_button, Some(1));
+        assert_eq!(before.data.cancel_button, Some(2));
+        // * out of range, the indices are detected from the new buttons
+        let dialog = MessageBox::new("", "")
+            .default_button(3)
+            .cancel_button(3)
+            .buttons(buttons());
+        assert_eq!(dialog.data.default_button, Some(0));
+        assert_eq!(dialog.data.cancel_button, Some(2));
+    }
+
+    #[test]
+    fn indices_after_presets() {
+        let dialog = MessageBox::new("", "")
+            .save_dont_save_cancel()
+            .default_button(1);
+        assert_eq!(dialog.data.default_button, Some(1));
+        assert_eq!(dialog.data.cancel_button, Some(2));
+
+        let dialog = MessageBox::question("", "").default_button(1);
+        assert_eq!(dialog.data.default_button, Some(1));
+        assert_eq!(dialog.data.cancel_button, Some(1));
+
+        let dialog = MessageBox::new("", "").abort_retry_ignore();
+        assert_eq!(dialog.data.default_button, Some(1));
+        assert_eq!(dialog.data.cancel_button, None);
+    }
+
+    #[test]
+    fn button_detects_cancel() {
+        let dialog = MessageBox::new("", "");
+        let dialog = buttons()
+            .into_iter()
+            .fold(dialog, |dialog, button| dialog.button(button));
+        assert_eq!(dialog.data.buttons.len(), 3);
+        assert_eq!(dialog.data.default_button, Some(0));
+        assert_eq!(dialog.data.cancel_button, Some(2));
+    }
+
+    #[test]
+    fn first_button_replaces_the_defaults() {
+        let dialog = MessageBox::new("", "")
+            .button(message_box_button("是").dialog_result(DialogResult::Yes))
+            .button(message_box_button("否").dialog_result(DialogResult::No));
+        assert_eq!(dialog.data.buttons.len(), 2);
+        assert_eq!(dialog.data.default_button, Some(0));
+        assert_eq!(dialog.data.cancel_button, None);
+
+        // * the buttons of a preset are kept
+        let dialog = MessageBox::info("", "")
+            .button(message_box_button("详情").dialog_result(DialogResult::Retry));
+        assert_eq!(dialog.data.buttons.len(), 2);
+        assert_eq!(dialog.data.cancel_button, Some(0));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn primary_and_secondary_shims() {
+        let dialog = MessageBox::new("", "")
+            .primary_button(message_box_button("是").dialog_result(DialogResult::Yes))
+            .hide_secondary_button();
+        assert_eq!(dialog.data.buttons.len(), 2);
+        assert_eq!(dialog.data.buttons[0].message, DialogResult::Yes);
+        assert!(!dialog.data.buttons[1].visible);
+    }
+}
@@ -1,8 +1,30 @@
 mod char_icon;
+#[cfg(feature = "color-dialog")]
+pub mod color_dialog;
+#[cfg(feature = "dock")]
+pub mod dock;
+#[cfg(feature = "file-dialog")]
+pub mod file_dialog;
+#[cfg(feature = "font-dialog")]
+pub mod font_dialog;
 mod inner_message_box;
+#[cfg(feature = "input-box")]
+pub mod input_box;
+pub mod job;
 pub mod message_box;
 pub mod multi_windows;
+#[cfg(feature = "navigator")]
+pub mod navigator;
+pub mod program_handle;
+#[cfg(feature = "progress-dialog")]
+pub mod progress_dialog;
+mod single_instance;
 pub mod table;
+#[cfg(feature = "theme")]
+pub mod theme;
+mod timer;
+#[cfg(feature = "wizard")]
+pub mod wizard;
 
 use char_icon::CharIcon;
-pub use inner_message_box::{message_box_button, MessageBoxButton};
+pub use inner_message_box::{message_box_button, MessageBoxButton, MessageBoxIcon};
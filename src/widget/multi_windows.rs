@@ -3,32 +3,384 @@
 use iced::keyboard::key::Named;
 use iced::keyboard::Key;
 use iced::widget::{column, container, opaque, stack};
-use iced::{event, window, Color, Element, Length, Subscription, Task, Theme};
+use iced::{
+    event, mouse, window, Color, Element, Length, Point, Size, Subscription, Task, Theme, Vector,
+};
 use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use super::job::{JobMessage, Jobs};
+use super::program_handle::{receiver_subscription, ProgramHandle, SharedReceiver};
+use super::single_instance::Instance;
+use super::timer;
 use crate::core::WindowCloseCallbackData;
-use crate::core::{DialogResult, EventMessage, IWindow, Message, Window};
+use crate::core::{
+    DialogResult, EventMessage, ExitVote, IWindow, Message, RequestError, Window, WindowMessage,
+    WindowPlacement,
+};
 
 /// run multiple window with a main window data
 pub fn run<W>(data: W, default_font: iced::Font) -> iced::Result
 where
     W: IWindow,
 {
-    // TODO: you should install font before starting
-    iced::daemon(Program::title, Program::update, Program::view)
-        .subscription(Program::subscription)
-        .theme(Program::theme)
-        .scale_factor(Program::scale_factor)
-        .settings(iced::Settings {
-            default_font, //iced::Font::with_name("微软雅黑"),
-            ..Default::default()
-        })
-        .run_with(move || Program::new(data))
+    application(data).default_font(default_font).run()
+}
+
+/// create a multiple windows application with a main window data
+/// * `application(data).default_font(font).exit_policy(policy).run()`
+pub fn application<W>(data: W) -> Application<W>
+where
+    W: IWindow,
+{
+    Application::new(data)
+}
+
+/// when to exit the program
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExitPolicy {
+    /// exit when the last window is closed
+    #[default]
+    LastWindowClosed,
+    /// exit when the main window is closed
+    MainWindowClosed,
+    /// only exit by `Message::Exit`
+    ExplicitOnly,
+}
+
+impl ExitPolicy {
+    /// whether closing the window exits the program
+    /// * `is_main` - whether the closing window is the main window
+    /// * `remaining` - the number of the open windows except the closing window
+    fn exits_on_close(&self, is_main: bool, remaining: usize) -> bool {
+        match self {
+            ExitPolicy::LastWindowClosed => remaining == 0,
+            ExitPolicy::MainWindowClosed => is_main || remaining == 0,
+            ExitPolicy::ExplicitOnly => false,
+        }
+    }
+}
+
+/// how to coalesce the high-frequency moved and resized events
+/// * the window position and size are always live, only the hooks are coalesced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventCoalescing {
+    /// call the hooks for every event
+    Off,
+    /// call the hooks with the latest value once per frame
+    #[default]
+    PerFrame,
+    /// call the hooks with the latest value after no event for the delay
+    Settle(Duration),
+}
+
+impl EventCoalescing {
+    /// whether the pending event is ready to call the hooks
+    fn is_ready(&self, pending: &PendingGeometry, now: Instant) -> bool {
+        match self {
+            EventCoalescing::Off | EventCoalescing::PerFrame => true,
+            EventCoalescing::Settle(delay) => now.saturating_duration_since(pending.last) >= *delay,
+        }
+    }
+}
+
+/// the limits of the nested messages produced by one message
+/// * a window answering its own message will loop forever, the limits stop it with a diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageLimit {
+    /// the max nesting depth, the deeper message is dropped
+    pub depth: usize,
+    /// the max count of the handled messages, the rest messages are dropped
+    pub iterations: usize,
+}
+
+impl Default for MessageLimit {
+    fn default() -> Self {
+        Self {
+            depth: 64,
+            iterations: 10_000,
+        }
+    }
+}
+
+/// a trait to add cross-cutting behaviour to the [Program], like audit logging or a debug overlay
+/// * register it by `application(data).extension(extension)`
+/// * the extensions are called in the order they were registered
+pub trait ProgramExtension {
+    /// called before the message is handled
+    /// # Arguments
+    /// * `origin` - the window produced the message, `None` for the events and the widgets
+    /// * `message` - the message to handle
+    /// # Return
+    /// * `None` - drop the message
+    /// * `Some(message)` - handle the message, it can be rewritten
+    fn before_update(&mut self, origin: Option<window::Id>, message: Message) -> Option<Message> {
+        let _ = origin;
+        Some(message)
+    }
+
+    /// called after the message is handled
+    /// # Arguments
+    /// * `origin` - the window produced the message
+    /// * `message` - the handled message
+    /// # Return
+    /// * a new message to handle, `Message::None` for nothing
+    fn after_update(&mut self, origin: Option<window::Id>, message: &Message) -> Message {
+        let _ = origin;
+        let _ = message;
+        Message::None
+    }
+
+    /// called after the window is opened
+    fn on_window_opened(&mut self, id: window::Id, window: &Window) -> Message {
+        let _ = id;
+        let _ = window;
+        Message::None
+    }
+
+    /// called before the window is removed
+    fn on_window_closed(&mut self, id: window::Id, window: &Window) -> Message {
+        let _ = id;
+        let _ = window;
+        Message::None
+    }
+
+    /// decorate the view of every window
+    /// # Arguments
+    /// * `id` - the window id
+    /// * `window` - the window
+    /// * `content` - the view of the window, including the modal overlay
+    fn view<'a>(
+        &'a self,
+        id: window::Id,
+        window: &'a Window,
+        content: Element<'a, Message>,
+    ) -> Element<'a, Message> {
+        let _ = id;
+        let _ = window;
+        content
+    }
+}
+
+/// a nested message waiting in the queue
+struct Queued {
+    message: Message,
+    /// the window produced the message
+    origin: Option<window::Id>,
+    depth: usize,
+}
+
+/// the coalesced moved and resized event of a window
+#[derive(Debug, Clone, Copy)]
+struct PendingGeometry {
+    moved: Option<Point>,
+    resized: Option<Size>,
+    /// the time of the last event
+    last: Instant,
+}
+
+impl PendingGeometry {
+    fn new() -> Self {
+        Self {
+            moved: None,
+            resized: None,
+            last: Instant::now(),
+        }
+    }
+}
+
+/// the multiple windows application builder
+pub struct Application<W> {
+    data: W,
+    default_font: iced::Font,
+    exit_policy: ExitPolicy,
+    coalescing: EventCoalescing,
+    limit: MessageLimit,
+    extensions: Vec<Box<dyn ProgramExtension>>,
+    single_instance: Option<String>,
+    handle: ProgramHandle,
+    receiver: SharedReceiver<Message>,
+    #[cfg(feature = "theme")]
+    theme_file: Option<std::path::PathBuf>,
+}
+
+impl<W> Application<W>
+where
+    W: IWindow,
+{
+    /// create an application with a main window data
+    pub fn new(data: W) -> Self {
+        let (handle, receiver) = ProgramHandle::channel();
+        Self {
+            data,
+            default_font: iced::Font::default(),
+            exit_policy: ExitPolicy::default(),
+            coalescing: EventCoalescing::default(),
+            limit: MessageLimit::default(),
+            extensions: Vec::new(),
+            single_instance: None,
+            handle,
+            receiver,
+            #[cfg(feature = "theme")]
+            theme_file: None,
+        }
+    }
+
+    /// get the handle to send messages from other threads, the messages are handled after running
+    pub fn handle(&self) -> ProgramHandle {
+        self.handle.clone()
+    }
+
+    /// set the default font
+    pub fn default_font(mut self, default_font: iced::Font) -> Self {
+        self.default_font = default_font;
+        self
+    }
+
+    /// set when to exit the program
+    pub fn exit_policy(mut self, exit_policy: ExitPolicy) -> Self {
+        self.exit_policy = exit_policy;
+        self
+    }
+
+    /// set how to coalesce the moved and resized events, default is once per frame
+    pub fn coalesce_events(mut self, coalescing: EventCoalescing) -> Self {
+        self.coalescing = coalescing;
+        self
+    }
+
+    /// set the limits of the nested messages
+    pub fn message_limit(mut self, limit: MessageLimit) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// register an extension of the program, see [ProgramExtension]
+    pub fn extension<E>(mut self, extension: E) -> Self
+    where
+        E: ProgramExtension + 'static,
+    {
+        self.extensions.push(Box::new(extension));
+        self
+    }
+
+    /// only run one instance of the app
+    /// * the later launch forwards its arguments to the running instance and exits
+    /// * the main window receives them by [IWindow::on_instance_launched]
+    /// * `name` - the unique name of the app, it names the local socket
+    pub fn single_instance(mut self, name: impl Into<String>) -> Self {
+        self.single_instance = Some(name.into());
+        self
+    }
+
+    /// use the custom theme from the palette file, the theme is reloaded when the file changes
+    /// * `path` - the TOML or JSON palette file, see [ThemePalette](super::theme::ThemePalette)
+    #[cfg(feature = "theme")]
+    pub fn theme_file(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.theme_file = Some(path.into());
+        self
+    }
+
+    /// run the application
+    pub fn run(self) -> iced::Result {
+        let Self {
+            data,
+            default_font,
+            exit_policy,
+            coalescing,
+            limit,
+            extensions,
+            single_instance,
+            handle,
+            receiver,
+            #[cfg(feature = "theme")]
+            theme_file,
+        } = self;
+        // ! forward the arguments to the running instance and exit
+        let instance = match single_instance.as_deref().map(Instance::acquire) {
+            Some(Ok(None)) => return Ok(()),
+            Some(Ok(instance)) => instance,
+            // ! another instance owns the socket, but it doesn't answer in time
+            Some(Err(e)) if e.kind() == std::io::ErrorKind::AddrInUse => {
+                eprintln!("iced_kim: the running instance doesn't answer: {}", e);
+                return Ok(());
+            }
+            Some(Err(e)) => {
+                eprintln!("iced_kim: can't start the single instance: {}", e);
+                None
+            }
+            None => None,
+        };
+        // * the socket and the token file are removed when the app exits
+        let guard = instance.clone();
+        // TODO: you should install font before starting
+        let result = iced::daemon(Program::title, Program::update, Program::view)
+            .subscription(Program::subscription)
+            .theme(Program::theme)
+            .scale_factor(Program::scale_factor)
+            .settings(iced::Settings {
+                default_font, //iced::Font::with_name("微软雅黑"),
+                ..Default::default()
+            })
+            .run_with(move || {
+                let (mut program, task) = Program::with_handle(data, handle, receiver);
+                program.exit_policy = exit_policy;
+                program.coalescing = coalescing;
+                program.limit = limit;
+                program.extensions = extensions;
+                program.instance = instance;
+                #[cfg(feature = "theme")]
+                if let Some(path) = theme_file {
+                    match super::theme::load_theme(&path) {
+                        Ok(theme) => program.set_theme(theme),
+                        Err(e) => eprintln!("iced_kim: {} ({})", e, path.display()),
+                    }
+                    program.theme_file = Some(path);
+                }
+                (program, task)
+            });
+        if let Some(instance) = guard {
+            instance.release();
+        }
+        result
+    }
 }
 
 /// multiple windows data
 pub struct Program {
     windows: BTreeMap<window::Id, Window>,
+    /// the main window id
+    main_id: window::Id,
+    /// when to exit the program
+    exit_policy: ExitPolicy,
+    /// the focused window id
+    focused: Option<window::Id>,
+    /// the last cursor position and its window, for `WindowPlacement::AtCursor`
+    cursor: Option<(window::Id, Point)>,
+    /// how to coalesce the moved and resized events
+    coalescing: EventCoalescing,
+    /// the coalesced moved and resized events of the windows
+    pending: BTreeMap<window::Id, PendingGeometry>,
+    /// the alive flags of the windows, shared with the window handles
+    alive: BTreeMap<window::Id, Arc<AtomicBool>>,
+    /// the limits of the nested messages
+    limit: MessageLimit,
+    /// the nested messages produced by the message being handled, with their origin window
+    nested: Vec<(Option<window::Id>, Message)>,
+    /// the registered extensions
+    extensions: Vec<Box<dyn ProgramExtension>>,
+    /// the running instance in the single instance mode
+    instance: Option<Instance>,
+    /// the running jobs, it is shared with the windows
+    jobs: Jobs,
+    /// the handle shared with other threads, and its receiver
+    handle: ProgramHandle,
+    receiver: SharedReceiver<Message>,
+    /// the watched palette file
+    #[cfg(feature = "theme")]
+    theme_file: Option<std::path::PathBuf>,
     theme: Theme,
     scale: f64,
     icon: Option<iced::window::Icon>,
@@ -42,13 +394,46 @@ impl Program {
     where
         W: IWindow,
     {
-        let new_window = data.new_window();
+        let (handle, receiver) = ProgramHandle::channel();
+        Self::with_handle(data, handle, receiver)
+    }
+
+    /// open the main window with the handle created by `Application::handle()`
+    fn with_handle<W>(
+        data: W,
+        handle: ProgramHandle,
+        receiver: SharedReceiver<Message>,
+    ) -> (Self, Task<Message>)
+    where
+        W: IWindow,
+    {
+        let mut new_window = data.new_window();
         let icon = new_window.settings.icon.clone();
+        let jobs = Jobs::default();
+        let theme = iced::Theme::CatppuccinLatte;
+        new_window.jobs = jobs.clone();
+        new_window.theme = theme.clone();
         let (id, open) = window::open(new_window.settings.clone());
         (
             Self {
                 windows: BTreeMap::from([(id, new_window)]),
-                theme: iced::Theme::CatppuccinLatte,
+                main_id: id,
+                exit_policy: ExitPolicy::default(),
+                focused: None,
+                cursor: None,
+                coalescing: EventCoalescing::default(),
+                pending: BTreeMap::new(),
+                alive: BTreeMap::from([(id, Arc::new(AtomicBool::new(true)))]),
+                limit: MessageLimit::default(),
+                nested: Vec::new(),
+                extensions: Vec::new(),
+                instance: None,
+                jobs,
+                handle,
+                receiver,
+                #[cfg(feature = "theme")]
+                theme_file: None,
+                theme,
                 scale: 1.0,
                 icon: icon,
             },
@@ -60,17 +445,103 @@ impl Program {
     pub fn title(&self, window: window::Id) -> String {
         self.windows
             .get(&window)
-            .map(|window| window.title.clone())
+            .map(|w| {
+                let title = w.data.title(window);
+                // * the derived title takes precedence again when it changes after the override
+                match title.is_empty() || w.overridden_title.as_ref() == Some(&title) {
+                    true => w.title.clone(),
+                    false => title,
+                }
+            })
             .unwrap_or("".to_string())
     }
 
     /// 数据交互的核心部分，因为只有此处有 mut ,所以修改参数全部通过此处转发
+    /// * the nested messages are handled by a queue in depth first order, see [MessageLimit]
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        let mut queue = vec![Queued {
+            message,
+            origin: None,
+            depth: 0,
+        }];
+        let mut tasks = vec![];
+        let mut iterations = 0;
+        while let Some(Queued {
+            message,
+            origin,
+            depth,
+        }) = queue.pop()
+        {
+            let message = match message {
+                Message::None => continue,
+                // * keep the order of the multiple messages, they have the same depth
+                Message::MultMessage(msgs) => {
+                    queue.extend(msgs.into_iter().rev().map(|message| Queued {
+                        message,
+                        origin,
+                        depth,
+                    }));
+                    continue;
+                }
+                message => message,
+            };
+            // ! stop the loop, the rest messages are dropped
+            iterations += 1;
+            if iterations > self.limit.iterations {
+                self.report_loop("iteration", self.limit.iterations, &message, origin);
+                break;
+            }
+            if depth > self.limit.depth {
+                self.report_loop("depth", self.limit.depth, &message, origin);
+                continue;
+            }
+            // * the extensions can drop or rewrite the message
+            let mut message = Some(message);
+            for extension in self.extensions.iter_mut() {
+                message = message.and_then(|message| extension.before_update(origin, message));
+            }
+            let message = match message {
+                None | Some(Message::None) => continue,
+                // * the rewritten multiple messages are nested
+                Some(Message::MultMessage(msgs)) => {
+                    queue.push(Queued {
+                        message: Message::MultMessage(msgs),
+                        origin,
+                        depth: depth + 1,
+                    });
+                    continue;
+                }
+                Some(message) => message,
+            };
+            let handled = (!self.extensions.is_empty()).then(|| message.clone());
+            tasks.push(self.process(message));
+            if let Some(handled) = handled {
+                for extension in self.extensions.iter_mut() {
+                    let message = extension.after_update(origin, &handled);
+                    if !matches!(message, Message::None) {
+                        self.nested.push((origin, message));
+                    }
+                }
+            }
+            let nested = std::mem::take(&mut self.nested);
+            queue.extend(nested.into_iter().rev().map(|(origin, message)| Queued {
+                message,
+                origin,
+                depth: depth + 1,
+            }));
+        }
+        self.nested.clear();
+        Task::batch(tasks)
+    }
+
+    /// handle one message, the nested messages are put into `self.nested`
+    fn process(&mut self, message: Message) -> Task<Message> {
         match message {
             // * you don't need care
             Message::TitleChanged(id, title) => {
                 if let Some(window) = self.windows.get_mut(&id) {
                     window.title = title;
+                    window.overridden_title = Some(window.data.title(id));
                 }
                 Task::none()
             }
@@ -78,41 +549,63 @@ impl Program {
                 let mut tasks = vec![];
                 match msg {
                     EventMessage::Close(id, dialog_result) => {
+                        // ! closing the window exits by the exit policy, every window can veto it
+                        // ! before anything is closed, including the closing window itself
+                        if self.windows.contains_key(&id)
+                            && self
+                                .exit_policy
+                                .exits_on_close(id == self.main_id, self.windows.len() - 1)
+                        {
+                            let vetoes = self.exit_votes();
+                            if !vetoes.is_empty() {
+                                self.nested.extend(vetoes);
+                                return Task::none();
+                            }
+                        }
                         // 从当前窗口找到父窗口相关信息
                         let (parent_id, call_back) = if let Some(window) = self.windows.get(&id) {
-                            (window.parent_id, window.window_closed_callback.clone())
+                            (window.parent_id, window.window_closed_callback)
                         } else {
                             (None, None)
                         };
                         // 执行子窗口窗口关闭前，父窗口回调
                         if let Some(parent_id) = parent_id {
-                            if let Some(parent) = self.windows.get_mut(&parent_id) {
+                            if let (Some(parent), Some(window)) =
+                                (self.windows.get(&parent_id), self.windows.get(&id))
+                            {
                                 // 检查回调
                                 if let Some(callback) = &call_back {
                                     let data = WindowCloseCallbackData {
                                         id: parent_id,
                                         dialog_result: dialog_result.clone(),
                                         window: &parent.data,
+                                        dialog: &window.data,
                                     };
-                                    let m1 = (*callback)(data);
-                                    tasks.push(self.update(m1));
+                                    let message = (*callback)(data);
+                                    self.emit(Some(parent_id), message);
                                 }
                             }
                         }
                         // 关闭子窗口
-                        let message = EventMessage::Closed(id, dialog_result).into();
-                        let cmd = self.update(message);
-                        tasks.push(cmd);
+                        self.emit(Some(id), EventMessage::Closed(id, dialog_result).into());
                         tasks.push(window::close(id));
                     }
-                    EventMessage::Opened { id, position, size } => match self.windows.get(&id) {
-                        Some(window) => {
+                    EventMessage::Opened { id, position, size } => {
+                        if let Some(window) = self.windows.get_mut(&id) {
+                            window.position = position;
+                            window.size = size;
+                            window.focused_window = self.focused;
                             let message = window.data.on_opened(id, position, size);
-                            let cmd = self.update(message);
-                            tasks.push(cmd);
+                            for extension in self.extensions.iter_mut() {
+                                let message = extension.on_window_opened(id, window);
+                                if !matches!(message, Message::None) {
+                                    self.nested.push((None, message));
+                                }
+                            }
+                            self.emit(Some(id), message);
+                            tasks.push(Self::query_state(id));
                         }
-                        None => {}
-                    },
+                    }
                     EventMessage::Closed(id, dialog_result) => {
                         let parent_id = {
                             let window = self.windows.get(&id);
@@ -125,63 +618,146 @@ impl Program {
                         // ! 2024-09-24 Kim: 移除父窗口的子窗口id
                         if let Some(parent_id) = parent_id {
                             if let Some(parent) = self.windows.get_mut(&parent_id) {
-                                parent.child_id = None;
+                                if parent.child_id == Some(id) {
+                                    parent.child_id = None;
+                                }
                             }
                         }
+                        if self.focused == Some(id) {
+                            self.set_focused(None);
+                        }
+                        if self.cursor.is_some_and(|(cursor_id, _)| cursor_id == id) {
+                            self.cursor = None;
+                        }
+                        self.pending.remove(&id);
+                        if let Some(alive) = self.alive.remove(&id) {
+                            alive.store(false, Ordering::Relaxed);
+                        }
+                        // ! the jobs can't report to the closed window
+                        self.jobs.cancel_owned(id);
                         // 移除缓存
                         let window = self.windows.get(&id);
                         if let Some(window) = window {
-                            let m1 = window.data.on_window_closed(id, dialog_result.to_owned());
-                            let cmd1 = self.update(m1);
+                            let message =
+                                window.data.on_window_closed(id, dialog_result.to_owned());
+                            for extension in self.extensions.iter_mut() {
+                                let message = extension.on_window_closed(id, window);
+                                if !matches!(message, Message::None) {
+                                    self.nested.push((None, message));
+                                }
+                            }
+                            self.emit(Some(id), message);
                             self.windows.remove(&id);
-                            tasks.push(cmd1);
-                            if self.windows.len() == 0 {
+                            // ! exit by the exit policy, the windows are asked by `EventMessage::Close`
+                            if self
+                                .exit_policy
+                                .exits_on_close(id == self.main_id, self.windows.len())
+                            {
                                 return iced::exit();
                             }
-                        } else if self.windows.len() == 0 {
-                            return iced::exit();
                         }
                     }
-                    EventMessage::Moved { id, x, y } => match self.windows.get(&id) {
-                        Some(window) => {
-                            let message = window.data.on_moved(id, x, y);
-                            let cmd = self.update(message);
-                            tasks.push(cmd);
+                    // * the geometry is always live, but the hooks are coalesced
+                    EventMessage::Moved { id, x, y } => {
+                        if let Some(window) = self.windows.get_mut(&id) {
+                            let position = Point::new(x, y);
+                            window.position = Some(position);
+                            tasks.push(self.coalesce(id, |pending| pending.moved = Some(position)));
                         }
-                        None => {}
-                    },
-                    EventMessage::Resized { id, width, height } => match self.windows.get(&id) {
-                        Some(window) => {
-                            let message = window.data.on_resized(id, width, height);
-                            let cmd = self.update(message);
-                            tasks.push(cmd);
+                    }
+                    EventMessage::Resized { id, width, height } => {
+                        if let Some(window) = self.windows.get_mut(&id) {
+                            let size = Size::new(width, height);
+                            window.size = size;
+                            tasks.push(self.coalesce(id, |pending| pending.resized = Some(size)));
                         }
-                        None => {}
-                    },
-                    EventMessage::CloseRequest(id) => match self.windows.get(&id) {
-                        Some(window) => {
+                    }
+                    EventMessage::Flush(now) => {
+                        let coalescing = self.coalescing;
+                        // ! the windows not settled yet wait for the next tick
+                        let ready: Vec<window::Id> = self
+                            .pending
+                            .iter()
+                            .filter(|(_, pending)| coalescing.is_ready(pending, now))
+                            .map(|(id, _)| *id)
+                            .collect();
+                        for id in ready {
+                            if let Some(pending) = self.pending.remove(&id) {
+                                tasks.push(self.deliver_geometry(id, pending));
+                            }
+                        }
+                    }
+                    // ! the window can't be closed until its modal child is closed
+                    EventMessage::CloseRequest(id) if self.is_blocked(id) => {
+                        tasks.push(self.activate_modal_child(id));
+                    }
+                    EventMessage::CloseRequest(id) => {
+                        if let Some(window) = self.windows.get(&id) {
                             let message = window.data.on_close_request(id);
-                            let cmd = self.update(message);
-                            tasks.push(cmd);
+                            self.emit(Some(id), message);
                         }
-                        None => {}
-                    },
-                    EventMessage::Focused(id) => match self.windows.get(&id) {
-                        Some(window) => {
+                    }
+                    // ! bring the modal child to the front instead of the window
+                    EventMessage::Focused(id) if self.is_blocked(id) => {
+                        tasks.push(self.activate_modal_child(id));
+                    }
+                    EventMessage::Focused(id) => {
+                        if self.windows.contains_key(&id) {
+                            self.set_focused(Some(id));
+                        }
+                        if let Some(window) = self.windows.get_mut(&id) {
+                            // * a restored window gets the focus
+                            if !window.focused {
+                                tasks.push(Self::query_state(id));
+                            }
+                            window.focused = true;
                             let message = window.data.on_focus(id);
-                            let cmd = self.update(message);
-                            tasks.push(cmd);
+                            self.emit(Some(id), message);
                         }
-                        None => {}
-                    },
-                    EventMessage::Unfocused(id) => match self.windows.get(&id) {
-                        Some(window) => {
+                    }
+                    EventMessage::Unfocused(id) => {
+                        if self.focused == Some(id) {
+                            self.set_focused(None);
+                        }
+                        if let Some(window) = self.windows.get_mut(&id) {
+                            // * a minimized window loses the focus
+                            if window.focused {
+                                tasks.push(Self::query_state(id));
+                            }
+                            window.focused = false;
                             let message = window.data.on_unfocus(id);
-                            let cmd = self.update(message);
-                            tasks.push(cmd);
+                            self.emit(Some(id), message);
                         }
-                        None => {}
-                    },
+                    }
+                    EventMessage::State {
+                        id,
+                        minimized,
+                        maximized,
+                    } => {
+                        if let Some(window) = self.windows.get_mut(&id) {
+                            window.minimized = minimized;
+                            window.maximized = maximized;
+                        }
+                    }
+                    EventMessage::CursorMoved { id, position } => {
+                        if self.windows.contains_key(&id) {
+                            self.cursor = Some((id, position));
+                        }
+                    }
+                    EventMessage::KeyPressed { id, key, modifiers } => {
+                        if let Some(window) = self.windows.get(&id) {
+                            let message = window.data.on_key_pressed(id, key, modifiers);
+                            self.emit(Some(id), message);
+                        }
+                    }
+                    // * only the main window handles the later launches
+                    EventMessage::InstanceLaunched(args) => {
+                        if let Some(window) = self.windows.get(&self.main_id) {
+                            let message = window.data.on_instance_launched(self.main_id, args);
+                            self.emit(Some(self.main_id), message);
+                            tasks.push(window::gain_focus(self.main_id));
+                        }
+                    }
                 }
 
                 Task::batch(tasks)
@@ -193,8 +769,23 @@ impl Program {
                     .windows
                     .iter()
                     .filter(|a| a.1.data_type_id == data_type_id)
-                    .next();
-                if next.is_none() {
+                    .next()
+                    .map(|(id, _)| *id);
+                if let Some(id) = next {
+                    // * give the handle of the existing window
+                    if let (Some(callback), Some(alive)) =
+                        (&window_data.opened_callback, self.alive.get(&id))
+                    {
+                        let message = callback.call(id, alive.clone());
+                        self.emit(Some(window_data.parent_id.unwrap_or(id)), message);
+                    }
+                    // * give the data back, otherwise it is lost
+                    if let Some(callback) = &window_data.refused_callback {
+                        let message = callback.call(window_data.data);
+                        self.emit(Some(window_data.parent_id.unwrap_or(id)), message);
+                    }
+                    Task::none()
+                } else {
                     let parent_id = window_data.parent_id;
                     let data = window_data.data;
                     let mut new_window = data.new_window();
@@ -204,9 +795,12 @@ impl Program {
                     }
                     new_window.parent_id = parent_id;
                     new_window.data_type_id = data_type_id;
+                    // * open the window relative to the parent window
+                    if let Some(position) = self.placement_position(parent_id, &new_window) {
+                        new_window.settings.position = window::Position::Specific(position);
+                    }
                     // check whether need callback when the window is destroyed
-                    if let Some(p) = window_data.callback {
-                        let callback = unsafe { Box::from_raw(p) };
+                    if let Some(callback) = window_data.callback {
                         new_window.window_closed_callback = Some(callback);
                         // TODO disable the parent window when show a alert dialog
                         // if let Some(parent) = self.windows.get_mut(&parent_id) {
@@ -215,58 +809,291 @@ impl Program {
                     }
                     // open a new window
                     let (id, open) = window::open(new_window.settings.clone());
+                    new_window.jobs = self.jobs.clone();
+                    new_window.theme = self.theme.clone();
                     self.windows.insert(id, new_window);
-                    // add child id to the parent window
-                    if let Some(parent_id) = parent_id {
+                    // add child id to the parent window, a modeless window doesn't block its parent
+                    if let Some(parent_id) = parent_id.filter(|_| window_data.modal) {
                         if let Some(parent) = self.windows.get_mut(&parent_id) {
                             parent.child_id = Some(id);
                         }
                     }
+                    let alive = Arc::new(AtomicBool::new(true));
+                    self.alive.insert(id, alive.clone());
+                    // give the new window handle to the parent
+                    if let Some(callback) = window_data.opened_callback {
+                        let message = callback.call(id, alive);
+                        self.emit(Some(parent_id.unwrap_or(id)), message);
+                    }
                     // ! to show the window
                     open.map(|id| Message::None)
-                } else {
-                    Task::none()
                 }
             }
             // ! 2024-03-29 Kim 在所有页面遍历传递消息，这样发送的时候就不用管ID了，只要发送对应数据就可以了，页面需要什么数据就监控什么数据。
             Message::WindowMessage(msg) => {
-                let mut msgs = Vec::new();
                 for (id, window) in self.windows.iter_mut() {
-                    let msg = window.data.update(&id.clone(), &msg.msg);
+                    let msg = window.data.update(&id.clone(), &msg.msg).with_origin(*id);
                     match msg {
                         Message::None => {}
                         _ => {
-                            msgs.push(msg);
+                            self.nested.push((Some(*id), msg));
                         }
                     }
                 }
-                self.update(msgs.into())
+                Task::none()
             }
-            Message::MultMessage(msgs) => {
-                let mut tasks = vec![];
-                for msg in msgs {
-                    tasks.push(self.update(msg));
-                }
-                Task::batch(tasks)
+            // * the reply is only sent to the requester
+            Message::Request(request) => {
+                // ! a request without requester has nowhere to reply, it is dropped
+                let Some(from) = request.from else {
+                    eprintln!(
+                        "iced_kim: the request {} has no requester and is dropped, use Message::request_from in the view",
+                        request.msg.type_name()
+                    );
+                    return Task::none();
+                };
+                let reply = match self.windows.get_mut(&request.target) {
+                    Some(window) => window
+                        .data
+                        .on_request(&request.target, from, &request.msg)
+                        .ok_or(RequestError::Unhandled),
+                    None => Err(RequestError::Closed),
+                };
+                let reply = (request.on_reply)(reply);
+                let message = Message::SendTo(from, WindowMessage::from_box(reply));
+                self.emit(Some(request.target), message);
+                Task::none()
             }
+            Message::SendTo(id, msg) => match self.windows.get_mut(&id) {
+                Some(window) => {
+                    let message = window.data.update(&id, &msg.msg);
+                    self.emit(Some(id), message);
+                    Task::none()
+                }
+                None => Task::none(),
+            },
+            Message::Focus(id) => window::gain_focus(id),
+            Message::Job(msg) => match msg {
+                JobMessage::Start(data) => self.jobs.start(data, self.handle.clone()),
+                JobMessage::Cancel(id) => {
+                    self.jobs.cancel(id);
+                    Task::none()
+                }
+                // * the result is only sent to the owner
+                JobMessage::Finished(id, result) => {
+                    if let Some(owner) = self.jobs.finish(id) {
+                        self.emit(None, Message::SendTo(owner, result));
+                    }
+                    Task::none()
+                }
+                JobMessage::Panicked(id) => {
+                    self.jobs.finish(id);
+                    Task::none()
+                }
+                JobMessage::Background(work) => work.perform(),
+            },
+            // * the multiple messages are flattened by the queue in `update`
+            Message::MultMessage(_) => Task::none(),
             Message::Theme(theme) => {
-                self.theme = theme;
+                self.set_theme(theme);
                 Task::none()
             }
             Message::Scale(scale) => {
                 self.scale = scale;
                 Task::none()
             }
-            Message::Exit => iced::exit(),
+            Message::Exit => self.exit(),
             Message::None => Task::none(),
         }
     }
 
+    /// get the window by its id
+    pub fn window(&self, id: window::Id) -> Option<&Window> {
+        self.windows.get(&id)
+    }
+
+    /// the focused window id
+    pub fn focused(&self) -> Option<window::Id> {
+        self.focused
+    }
+
+    /// get the handle to send messages from other threads
+    pub fn handle(&self) -> ProgramHandle {
+        self.handle.clone()
+    }
+
+    /// put the nested message produced by the window into the queue
+    fn emit(&mut self, origin: Option<window::Id>, message: Message) {
+        let message = match origin {
+            Some(id) => message.with_origin(id),
+            None => message,
+        };
+        if !matches!(message, Message::None) {
+            self.nested.push((origin, message));
+        }
+    }
+
+    /// print the diagnostic of the message loop
+    fn report_loop(&self, kind: &str, limit: usize, message: &Message, origin: Option<window::Id>) {
+        let origin = match origin {
+            Some(id) => format!("window {:?} \"{}\"", id, self.title(id)),
+            None => "the program".to_string(),
+        };
+        eprintln!(
+            "iced_kim: the message {} limit {} is exceeded by {} from {}, it may be a message loop",
+            kind,
+            limit,
+            message.type_name(),
+            origin
+        );
+    }
+
+    /// ask every window before exiting, any of them can veto
+    fn exit(&mut self) -> Task<Message> {
+        let vetoes = self.exit_votes();
+        if vetoes.is_empty() {
+            iced::exit()
+        } else {
+            self.nested.extend(vetoes);
+            Task::none()
+        }
+    }
+
+    /// ask every window whether to exit, and get the messages of the vetoes
+    fn exit_votes(&mut self) -> Vec<(Option<window::Id>, Message)> {
+        let mut vetoes = Vec::new();
+        for (id, window) in self.windows.iter_mut() {
+            if let ExitVote::Veto(message) = window.data.on_exit(*id) {
+                vetoes.push((Some(*id), message.with_origin(*id)));
+            }
+        }
+        vetoes
+    }
+
+    /// coalesce the moved or resized event of the window
+    /// * the pending events are delivered by `EventMessage::Flush`, see [Program::subscription]
+    /// * `merge` - merge the latest value to the pending event
+    fn coalesce(
+        &mut self,
+        id: window::Id,
+        merge: impl FnOnce(&mut PendingGeometry),
+    ) -> Task<Message> {
+        if self.coalescing == EventCoalescing::Off {
+            let mut pending = PendingGeometry::new();
+            merge(&mut pending);
+            return self.deliver_geometry(id, pending);
+        }
+        let pending = self.pending.entry(id).or_insert_with(PendingGeometry::new);
+        merge(pending);
+        // * the settle delay restarts by every event
+        pending.last = Instant::now();
+        Task::none()
+    }
+
+    /// call the moved and resized hooks with the latest values
+    /// * minimizing or maximizing the window resizes it, so the state is queried once after it
+    fn deliver_geometry(&mut self, id: window::Id, pending: PendingGeometry) -> Task<Message> {
+        let Some(window) = self.windows.get(&id) else {
+            return Task::none();
+        };
+        let mut msgs = Vec::new();
+        if let Some(position) = pending.moved {
+            msgs.push(window.data.on_moved(id, position.x, position.y));
+        }
+        if let Some(size) = pending.resized {
+            msgs.push(window.data.on_resized(id, size.width, size.height));
+        }
+        self.emit(Some(id), msgs.into());
+        match pending.resized {
+            Some(_) => Self::query_state(id),
+            None => Task::none(),
+        }
+    }
+
+    /// whether the window has an open modal child
+    fn is_blocked(&self, id: window::Id) -> bool {
+        self.windows
+            .get(&id)
+            .and_then(|window| window.child_id)
+            .is_some_and(|child_id| self.windows.contains_key(&child_id))
+    }
+
+    /// bring the modal child of the window to the front, and flash it if needed
+    fn activate_modal_child(&self, id: window::Id) -> Task<Message> {
+        let Some(child_id) = self.windows.get(&id).and_then(|window| window.child_id) else {
+            return Task::none();
+        };
+        match self.windows.get(&child_id) {
+            Some(child) if child.flash => Task::batch([
+                window::gain_focus(child_id),
+                window::request_user_attention(
+                    child_id,
+                    Some(window::UserAttention::Informational),
+                ),
+            ]),
+            Some(_) => window::gain_focus(child_id),
+            None => Task::none(),
+        }
+    }
+
+    /// share the theme with every window
+    fn set_theme(&mut self, theme: Theme) {
+        for window in self.windows.values_mut() {
+            window.theme = theme.clone();
+        }
+        self.theme = theme;
+    }
+
+    /// share the focused window with every window
+    fn set_focused(&mut self, focused: Option<window::Id>) {
+        self.focused = focused;
+        for window in self.windows.values_mut() {
+            window.focused_window = focused;
+        }
+    }
+
+    /// query the minimized and maximized state, iced doesn't send events for them
+    /// * it is queried when the window is opened, the coalesced resize is delivered or the focus changes
+    /// * both states are answered by one message
+    fn query_state(id: window::Id) -> Task<Message> {
+        window::get_minimized(id).then(move |minimized| {
+            window::get_maximized(id).map(move |maximized| {
+                EventMessage::State {
+                    id,
+                    minimized: minimized.unwrap_or(false),
+                    maximized,
+                }
+                .into()
+            })
+        })
+    }
+
+    /// the position of the new window by its placement
+    fn placement_position(&self, parent_id: Option<window::Id>, window: &Window) -> Option<Point> {
+        let parent = self.windows.get(&parent_id?)?;
+        let origin = parent.position?;
+        let size = window.settings.size;
+        match window.placement {
+            WindowPlacement::Default => None,
+            WindowPlacement::CenteredOnParent => Some(Point::new(
+                origin.x + (parent.size.width - size.width) / 2.0,
+                origin.y + (parent.size.height - size.height) / 2.0,
+            )),
+            WindowPlacement::Cascade(offset) => Some(origin + offset),
+            WindowPlacement::AtCursor => {
+                let (id, cursor) = self.cursor?;
+                let window = self.windows.get(&id)?;
+                let origin = window.position?;
+                Some(origin + Vector::new(cursor.x, cursor.y))
+            }
+        }
+    }
+
     /// you don't need care
     pub fn view(&self, id: window::Id) -> Element<Message> {
         if let Some(window) = self.windows.get(&id) {
             let content = window.data.view(window, id);
-            if let Some(child_id) = window.child_id {
+            let content = if let Some(child_id) = window.child_id {
                 // ! 2024-09-24增加窗口模态
                 stack![
                     content,
@@ -291,7 +1118,11 @@ impl Program {
                 .into()
             } else {
                 content
-            }
+            };
+            // * the extensions decorate the view, the first registered is the innermost
+            self.extensions.iter().fold(content, |content, extension| {
+                extension.view(id, window, content)
+            })
         } else {
             column!().into()
         }
@@ -310,7 +1141,7 @@ impl Program {
     /// 订阅窗口事件，不用关注
     /// todo 输入法切换
     pub fn subscription(&self) -> Subscription<Message> {
-        event::listen_with(|event, status, id| {
+        let events = event::listen_with(|event, status, id| {
             if let iced::Event::Window(window_event) = event {
                 match window_event {
                     window::Event::Opened { position, size } => {
@@ -354,6 +1185,10 @@ impl Program {
                 }
             } else {
                 match event {
+                    // * remember the cursor for the window placement
+                    iced::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                        Some(EventMessage::CursorMoved { id, position }.into())
+                    }
                     iced::Event::Keyboard(ke) => match ke {
                         iced::keyboard::Event::KeyPressed {
                             key,
@@ -386,13 +1221,195 @@ impl Program {
                                 //     winit::event::Ime::Disabled => None,
                                 // },
                             }
-                            None
+                            // * only the keys which no widget captured
+                            if status == event::Status::Ignored {
+                                let msg = EventMessage::KeyPressed { id, key, modifiers };
+                                Some(msg.into())
+                            } else {
+                                None
+                            }
                         }
                         _ => None,
                     },
                     _ => None,
                 }
             }
+        });
+        // * the messages sent by the program handles
+        let handle = receiver_subscription("program-handle", self.receiver.clone());
+        let mut subscriptions = vec![events, handle];
+        // * deliver the coalesced events, only while some events are pending
+        if !self.pending.is_empty() {
+            let flush = match self.coalescing {
+                EventCoalescing::Settle(delay) => {
+                    let interval = (delay / 4).max(Duration::from_millis(16));
+                    timer::every("event-coalescing", interval)
+                }
+                _ => window::frames(),
+            };
+            subscriptions.push(flush.map(|now| EventMessage::Flush(now).into()));
+        }
+        // * the arguments forwarded by the later launches
+        if let Some(instance) = &self.instance {
+            subscriptions.push(
+                instance
+                    .subscription()
+                    .map(|args| EventMessage::InstanceLaunched(args).into()),
+            );
+        }
+        // * reload the theme when the palette file changes
+        #[cfg(feature = "theme")]
+        if let Some(path) = &self.theme_file {
+            subscriptions.push(super::theme::watch_theme(path.clone()));
+        }
+        Subscription::batch(subscriptions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::job::BackgroundWork;
+    use std::sync::atomic::AtomicUsize;
+
+    #[derive(Debug, Clone)]
+    struct Ping;
+
+    impl crate::core::IWindowMessage for Ping {}
+
+    /// answers every message with `fan_out` new pings
+    #[derive(Debug, Clone)]
+    struct Echo {
+        count: Arc<AtomicUsize>,
+        fan_out: usize,
+    }
+
+    impl IWindow for Echo {
+        fn new_window(&self) -> Window {
+            Window::builder(self.clone()).build()
+        }
+
+        fn view(&self, _window: &Window, _id: window::Id) -> Element<'_, Message> {
+            iced::widget::column![].into()
+        }
+
+        fn update(
+            &mut self,
+            _id: &window::Id,
+            _message: &Box<dyn crate::core::IWindowMessage>,
+        ) -> Message {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            let messages: Vec<Message> = (0..self.fan_out)
+                .map(|_| Message::window_message(Ping))
+                .collect();
+            messages.into()
+        }
+    }
+
+    fn echo_program(fan_out: usize, limit: MessageLimit) -> (Program, Arc<AtomicUsize>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (mut program, _) = Program::new(Echo {
+            count: count.clone(),
+            fan_out,
+        });
+        program.limit = limit;
+        (program, count)
+    }
+
+    #[test]
+    fn exit_policy_on_close() {
+        let policy = ExitPolicy::LastWindowClosed;
+        assert!(!policy.exits_on_close(true, 1));
+        assert!(!policy.exits_on_close(false, 1));
+        assert!(policy.exits_on_close(false, 0));
+
+        let policy = ExitPolicy::MainWindowClosed;
+        assert!(policy.exits_on_close(true, 2));
+        assert!(!policy.exits_on_close(false, 2));
+        assert!(policy.exits_on_close(false, 0));
+
+        let policy = ExitPolicy::ExplicitOnly;
+        assert!(!policy.exits_on_close(true, 0));
+        assert!(!policy.exits_on_close(false, 0));
+    }
+
+    #[test]
+    fn message_limit_depth() {
+        let (mut program, count) = echo_program(
+            1,
+            MessageLimit {
+                depth: 3,
+                iterations: 100,
+            },
+        );
+        let _ = program.update(Message::window_message(Ping));
+        // the first message and three nested generations
+        assert_eq!(count.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn message_limit_iterations() {
+        let (mut program, count) = echo_program(
+            2,
+            MessageLimit {
+                depth: 64,
+                iterations: 10,
+            },
+        );
+        let _ = program.update(Message::window_message(Ping));
+        assert_eq!(count.load(Ordering::SeqCst), 10);
+    }
+
+    #[test]
+    fn request_without_requester_is_dropped() {
+        let (mut program, count) = echo_program(1, MessageLimit::default());
+        let target = program.main_id;
+        let _ = program.update(Message::request(target, Ping, |_: Result<Ping, _>| Ping));
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn cursor_placement() {
+        let (mut program, _) = echo_program(0, MessageLimit::default());
+        let main_id = program.main_id;
+        let _ = program.update(
+            EventMessage::Opened {
+                id: main_id,
+                position: Some(Point::new(100.0, 50.0)),
+                size: Size::new(800.0, 600.0),
+            }
+            .into(),
+        );
+        let child = Window::builder(Echo {
+            count: Arc::new(AtomicUsize::new(0)),
+            fan_out: 0,
         })
+        .placement(WindowPlacement::AtCursor)
+        .build();
+        assert_eq!(program.placement_position(Some(main_id), &child), None);
+
+        let _ = program.update(
+            EventMessage::CursorMoved {
+                id: main_id,
+                position: Point::new(10.0, 20.0),
+            }
+            .into(),
+        );
+        assert_eq!(
+            program.placement_position(Some(main_id), &child),
+            Some(Point::new(110.0, 70.0))
+        );
+
+        // ! the cursor of a closed window is forgotten
+        let _ = program.update(EventMessage::Closed(main_id, DialogResult::None).into());
+        assert_eq!(program.cursor, None);
+    }
+
+    #[test]
+    fn background_work_is_not_a_job() {
+        let (mut program, _) = echo_program(0, MessageLimit::default());
+        let owner = program.main_id;
+        let _ = program.update(BackgroundWork::start(owner, || Ping));
+        assert!(program.jobs.list().is_empty());
     }
 }
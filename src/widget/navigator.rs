@@ -0,0 +1,245 @@
+// ! a page navigation stack inside one window
+// ! push a page to go forward, pop a page to go back with a result for the page underneath
+
+use iced::keyboard::key::Named;
+use iced::keyboard::{Key, Modifiers};
+use iced::widget::{button, column, container, row, text};
+use iced::{window, Alignment, Element, Length};
+
+use crate::core::{IWindowMessage, Message, Window};
+
+/// create a new navigator with the root page
+/// * `page` - the root page, it can't be popped
+pub fn navigator<T>(page: T) -> Navigator
+where
+    T: IPage,
+{
+    Navigator::new(page)
+}
+
+/// a trait for a page of the [Navigator]
+pub trait IPage: dyn_clone::DynClone + downcast_rs::Downcast + Send {
+    /// the page title, it will be the window title when the page is on the top
+    fn title(&self) -> String;
+
+    /// get the view of the page
+    /// # Arguments
+    /// * `window` - the host window
+    /// * `id` - the host window id
+    fn view(&self, window: &Window, id: window::Id) -> Element<'_, Message>;
+
+    /// this is where the message is handled when the page is on the top
+    /// # Arguments
+    /// * `id` - the host window id
+    /// * `message` - the global message, you need check the message type
+    fn update(
+        &mut self,
+        id: &window::Id,
+        message: &std::boxed::Box<dyn IWindowMessage>,
+    ) -> Message {
+        let _ = id;
+        let _ = message;
+        Message::None
+    }
+
+    /// the page above was popped with a result
+    /// # Arguments
+    /// * `id` - the host window id
+    /// * `result` - the result of the popped page, you need check the result type
+    fn on_result(
+        &mut self,
+        id: &window::Id,
+        result: &std::boxed::Box<dyn IWindowMessage>,
+    ) -> Message {
+        let _ = id;
+        let _ = result;
+        Message::None
+    }
+}
+dyn_clone::clone_trait_object!(IPage);
+downcast_rs::impl_downcast!(IPage);
+impl std::fmt::Debug for dyn IPage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.title())
+    }
+}
+
+/// the navigator message, the window id is the host window of the navigator
+#[derive(Debug, Clone)]
+pub enum NavigatorMessage {
+    /// go forward to a new page
+    Push(window::Id, Box<dyn IPage>),
+    /// go back with an optional result for the page underneath
+    Pop(window::Id, Option<Box<dyn IWindowMessage>>),
+    /// replace the top page
+    Replace(window::Id, Box<dyn IPage>),
+}
+
+impl IWindowMessage for NavigatorMessage {}
+
+impl NavigatorMessage {
+    /// a message to go forward to a new page
+    /// * `id` - the host window id
+    /// * `page` - the new page
+    pub fn push<T>(id: window::Id, page: T) -> Message
+    where
+        T: IPage,
+    {
+        NavigatorMessage::Push(id, Box::new(page)).into()
+    }
+
+    /// a message to go back without result
+    /// * `id` - the host window id
+    pub fn pop(id: window::Id) -> Message {
+        NavigatorMessage::Pop(id, None).into()
+    }
+
+    /// a message to go back with a result for the page underneath
+    /// * `id` - the host window id
+    /// * `result` - the result, see [IPage::on_result]
+    pub fn pop_with<T>(id: window::Id, result: T) -> Message
+    where
+        T: IWindowMessage,
+    {
+        NavigatorMessage::Pop(id, Some(Box::new(result))).into()
+    }
+
+    /// a message to replace the top page
+    /// * `id` - the host window id
+    /// * `page` - the new page
+    pub fn replace<T>(id: window::Id, page: T) -> Message
+    where
+        T: IPage,
+    {
+        NavigatorMessage::Replace(id, Box::new(page)).into()
+    }
+}
+
+/// ! the navigator
+/// * call [Navigator::update] in the host window update
+/// * call [Navigator::view] in the host window view
+/// * call [Navigator::on_key_pressed] in the host window `on_key_pressed` for Alt+Left
+#[derive(Debug, Clone)]
+pub struct Navigator {
+    pages: Vec<Box<dyn IPage>>,
+    show_back: bool,
+}
+
+impl Navigator {
+    /// create a new navigator with the root page
+    pub fn new<T>(page: T) -> Self
+    where
+        T: IPage,
+    {
+        Self {
+            pages: vec![Box::new(page)],
+            show_back: true,
+        }
+    }
+
+    /// whether to show the back button bar, default is true
+    pub fn show_back(mut self, show_back: bool) -> Self {
+        self.show_back = show_back;
+        self
+    }
+
+    /// the title of the top page
+    pub fn title(&self) -> String {
+        self.top().title()
+    }
+
+    /// the count of pages in the stack
+    pub fn depth(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// whether the navigator can go back
+    pub fn can_go_back(&self) -> bool {
+        self.pages.len() > 1
+    }
+
+    /// the top page
+    pub fn top(&self) -> &dyn IPage {
+        self.pages
+            .last()
+            .map(|page| page.as_ref())
+            .expect("the navigator has no page")
+    }
+
+    /// handle the navigator message and forward other messages to the top page
+    /// # Arguments
+    /// * `id` - the host window id
+    /// * `message` - the message from the host window update
+    #[allow(clippy::borrowed_box)]
+    pub fn update(&mut self, id: &window::Id, message: &Box<dyn IWindowMessage>) -> Message {
+        let Some(msg) = message.downcast_ref::<NavigatorMessage>() else {
+            return match self.pages.last_mut() {
+                Some(page) => page.update(id, message),
+                None => Message::None,
+            };
+        };
+        match msg.clone() {
+            NavigatorMessage::Push(host, page) if host == *id => {
+                self.pages.push(page);
+                Message::TitleChanged(host, self.title())
+            }
+            NavigatorMessage::Replace(host, page) if host == *id => {
+                if let Some(top) = self.pages.last_mut() {
+                    *top = page;
+                }
+                Message::TitleChanged(host, self.title())
+            }
+            NavigatorMessage::Pop(host, result) if host == *id => {
+                if !self.can_go_back() {
+                    return Message::None;
+                }
+                self.pages.pop();
+                let mut msgs = vec![Message::TitleChanged(host, self.title())];
+                if let (Some(result), Some(page)) = (result, self.pages.last_mut()) {
+                    msgs.push(page.on_result(id, &result));
+                }
+                msgs.into()
+            }
+            _ => Message::None,
+        }
+    }
+
+    /// go back when Alt+Left is pressed
+    /// # Arguments
+    /// * `id` - the host window id
+    /// * `key` - the pressed key
+    /// * `modifiers` - the keyboard modifiers
+    pub fn on_key_pressed(&self, id: window::Id, key: Key, modifiers: Modifiers) -> Message {
+        if modifiers.alt() && key == Key::Named(Named::ArrowLeft) && self.can_go_back() {
+            NavigatorMessage::pop(id)
+        } else {
+            Message::None
+        }
+    }
+
+    /// get the view of the top page
+    /// # Arguments
+    /// * `window` - the host window
+    /// * `id` - the host window id
+    pub fn view<'a>(&'a self, window: &'a Window, id: window::Id) -> Element<'a, Message> {
+        let page = container(self.top().view(window, id))
+            .width(Length::Fill)
+            .height(Length::Fill);
+        if !self.show_back {
+            return page.into();
+        }
+
+        let back = button(text("←").size(16))
+            .style(button::text)
+            .on_press_maybe(self.can_go_back().then(|| NavigatorMessage::pop(id)));
+        let bar = row![back, text(self.title()).size(16)]
+            .spacing(10)
+            .padding(5)
+            .align_y(Alignment::Center);
+
+        column![bar, page]
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
@@ -0,0 +1,110 @@
+// ! a handle to send messages into the running program from other threads
+
+use iced::futures::channel::mpsc;
+use iced::futures::{stream, StreamExt};
+use iced::{window, Subscription};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+use crate::core::{IWindowMessage, Message, WindowMessage};
+
+/// the receiver of a channel, it is taken once by [receiver_subscription]
+pub(crate) type SharedReceiver<T> = Arc<Mutex<Option<mpsc::UnboundedReceiver<T>>>>;
+
+/// a cloneable handle to send messages into the running program, it can be moved to any thread
+/// * get it by `Application::handle()` before running, or `Program::handle()`
+#[derive(Debug, Clone)]
+pub struct ProgramHandle {
+    sender: mpsc::UnboundedSender<Message>,
+}
+
+impl ProgramHandle {
+    /// create a handle and the receiver for the program
+    pub(crate) fn channel() -> (Self, SharedReceiver<Message>) {
+        let (sender, receiver) = mpsc::unbounded();
+        (Self { sender }, Arc::new(Mutex::new(Some(receiver))))
+    }
+
+    /// send a message to the program, it is handled like a message from the view
+    /// # Arguments
+    /// * `message` - the message, it is `Send` because the window data and messages are `Send`
+    /// # Return
+    /// * `false` - the program has exited
+    pub fn send(&self, message: Message) -> bool {
+        self.sender.unbounded_send(message).is_ok()
+    }
+
+    /// send a window message only to the window
+    /// # Arguments
+    /// * `id` - the window to receive the message
+    /// * `msg` - the window message
+    /// # Return
+    /// * `false` - the program has exited
+    pub fn send_to<T>(&self, id: window::Id, msg: T) -> bool
+    where
+        T: IWindowMessage,
+    {
+        self.send(Message::send_to(id, msg))
+    }
+
+    /// send a window message to every window
+    /// # Arguments
+    /// * `msg` - the window message
+    /// # Return
+    /// * `false` - the program has exited
+    pub fn broadcast<T>(&self, msg: T) -> bool
+    where
+        T: IWindowMessage,
+    {
+        self.send(Message::WindowMessage(WindowMessage::new(msg)))
+    }
+
+    /// exit the program
+    /// # Return
+    /// * `false` - the program has exited
+    pub fn exit(&self) -> bool {
+        self.send(Message::Exit)
+    }
+
+    /// whether the program has exited
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
+
+/// a subscription of the values from the channel
+/// * the receiver is taken once when the subscription starts
+pub(crate) fn receiver_subscription<I, T>(id: I, receiver: SharedReceiver<T>) -> Subscription<T>
+where
+    I: Hash + 'static,
+    T: Send + 'static,
+{
+    Subscription::run_with_id(
+        id,
+        stream::once(async move { receiver.lock().ok().and_then(|mut r| r.take()) })
+            .filter_map(|receiver| async move { receiver })
+            .flatten(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_send<T: Send>() {}
+
+    #[test]
+    fn handle_is_send() {
+        assert_send::<Message>();
+        assert_send::<ProgramHandle>();
+    }
+
+    #[test]
+    fn send_after_exit() {
+        let (handle, receiver) = ProgramHandle::channel();
+        assert!(handle.send(Message::Exit));
+        drop(receiver);
+        assert!(handle.is_closed());
+        assert!(!handle.send(Message::Exit));
+    }
+}
@@ -0,0 +1,377 @@
+// ! a modal progress dialog
+// ! the dialog is closed with `DialogResult::Ok` when finished or `DialogResult::Cancel` by cancel
+
+use iced::advanced::renderer::{self, Quad};
+use iced::advanced::widget::tree::{self, Tree};
+use iced::advanced::{layout, Clipboard, Layout, Shell, Widget};
+use iced::event::{self, Event};
+use iced::time::Instant;
+use iced::widget::{button, column, progress_bar, row, text};
+use iced::window::{Level, RedrawRequest};
+use iced::{mouse, window, Alignment, Element, Length, Rectangle, Size, Theme};
+use std::time::Duration;
+
+use super::{message_box_button, MessageBoxButton};
+use crate::core::{
+    DialogResult, EventMessage, IWindow, IWindowMessage, Message, Window, WindowCloseCallback,
+    WindowPlacement,
+};
+
+/// the interval to animate the indeterminate bar
+const TICK: Duration = Duration::from_millis(50);
+/// the time of the indeterminate bar to cross the track
+const PERIOD: Duration = Duration::from_millis(2000);
+/// the width of the indeterminate bar relative to the track
+const SEGMENT: f32 = 0.25;
+
+/// create a new progress dialog with a new window
+/// * `title` - the title of the progress dialog
+pub fn progress_dialog(title: impl Into<String>) -> ProgressDialog {
+    ProgressDialog::new(title)
+}
+
+/// the progress dialog message, the window id is the parent window which showed the dialog
+#[derive(Debug, Clone)]
+pub enum ProgressMessage {
+    /// set the progress in `0.0..=1.0`, `None` is indeterminate
+    Progress(window::Id, Option<f32>),
+    /// set the status line
+    Status(window::Id, String),
+    /// close the dialog with `DialogResult::Ok`
+    Finish(window::Id),
+}
+
+impl IWindowMessage for ProgressMessage {}
+
+impl ProgressMessage {
+    /// a message to set the progress
+    /// * `parent` - the window which showed the dialog
+    /// * `value` - the progress in `0.0..=1.0`
+    pub fn progress(parent: window::Id, value: f32) -> Message {
+        ProgressMessage::Progress(parent, Some(value)).into()
+    }
+
+    /// a message to make the progress indeterminate
+    /// * `parent` - the window which showed the dialog
+    pub fn indeterminate(parent: window::Id) -> Message {
+        ProgressMessage::Progress(parent, None).into()
+    }
+
+    /// a message to set the status line
+    /// * `parent` - the window which showed the dialog
+    /// * `status` - the status
+    pub fn status(parent: window::Id, status: impl Into<String>) -> Message {
+        ProgressMessage::Status(parent, status.into()).into()
+    }
+
+    /// a message to close the dialog with `DialogResult::Ok`
+    /// * `parent` - the window which showed the dialog
+    pub fn finish(parent: window::Id) -> Message {
+        ProgressMessage::Finish(parent).into()
+    }
+}
+
+/// ! the progress dialog window data
+#[derive(Debug, Clone)]
+pub struct ProgressDialogData {
+    pub title: String,
+    /// the window which showed the dialog
+    pub parent: Option<window::Id>,
+    /// the progress in `0.0..=1.0`, `None` is indeterminate
+    pub value: Option<f32>,
+    pub status: String,
+    pub cancel: MessageBoxButton,
+    /// close the dialog with `DialogResult::Ok` when the progress reaches 1.0
+    pub close_on_complete: bool,
+    pub window_settings: iced::window::Settings,
+}
+
+impl Default for ProgressDialogData {
+    fn default() -> Self {
+        Self {
+            title: "请稍候".into(),
+            parent: None,
+            value: Some(0.0),
+            status: String::new(),
+            cancel: message_box_button("取消").dialog_result(DialogResult::Cancel),
+            close_on_complete: true,
+            window_settings: iced::window::Settings {
+                min_size: Some(iced::Size::new(400.0, 160.0)),
+                size: iced::Size::new(400.0, 160.0),
+                position: iced::window::Position::Centered,
+                level: Level::AlwaysOnTop,
+                resizable: false,
+                icon: None,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl ProgressDialogData {
+    /// whether the message is for this dialog
+    fn is_mine(&self, parent: window::Id) -> bool {
+        self.parent == Some(parent)
+    }
+}
+
+impl IWindow for ProgressDialogData {
+    fn new_window(&self) -> Window {
+        Window::builder(self.to_owned())
+            .title(self.title.clone())
+            .settings(self.window_settings.clone())
+            .placement(WindowPlacement::CenteredOnParent)
+            .build()
+    }
+
+    fn update(
+        &mut self,
+        id: &window::Id,
+        message: &std::boxed::Box<dyn IWindowMessage>,
+    ) -> Message {
+        match message.downcast_ref::<ProgressMessage>() {
+            Some(ProgressMessage::Progress(parent, value)) if self.is_mine(*parent) => {
+                self.value = value.map(|value| value.clamp(0.0, 1.0));
+                match self.value {
+                    Some(value) if value >= 1.0 && self.close_on_complete => {
+                        EventMessage::Close(*id, DialogResult::Ok).into()
+                    }
+                    _ => Message::None,
+                }
+            }
+            Some(ProgressMessage::Status(parent, status)) if self.is_mine(*parent) => {
+                self.status = status.clone();
+                Message::None
+            }
+            Some(ProgressMessage::Finish(parent)) if self.is_mine(*parent) => {
+                EventMessage::Close(*id, DialogResult::Ok).into()
+            }
+            _ => Message::None,
+        }
+    }
+
+    fn on_close_request(&self, id: window::Id) -> Message {
+        EventMessage::Close(id, self.cancel.message.clone()).into()
+    }
+
+    fn view(&self, _window: &Window, id: window::Id) -> Element<'_, Message> {
+        let bar: Element<'_, Message> = match self.value {
+            Some(value) => row![
+                progress_bar(0.0..=1.0, value).height(12),
+                text(format!("{:.0}%", value * 100.0)).width(40),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .into(),
+            None => IndeterminateBar.into(),
+        };
+
+        let button_cancel = self
+            .cancel
+            .to_button()
+            .on_press(EventMessage::Close(id, self.cancel.message.clone()).into())
+            .style(button::secondary);
+
+        let button_layout = column![]
+            .push_maybe(self.cancel.visible.then_some(button_cancel))
+            .align_x(Alignment::End)
+            .width(Length::Fill);
+
+        column![text(&self.status).height(Length::Fill), bar, button_layout]
+            .spacing(20)
+            .padding(10)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
+
+/// ! the progress dialog builder
+pub struct ProgressDialog {
+    data: ProgressDialogData,
+    callback_closed: Option<WindowCloseCallback>,
+}
+
+#[allow(unused)]
+impl ProgressDialog {
+    /// create a new progress dialog with the title
+    /// * `title` - the title of the dialog window
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            data: ProgressDialogData {
+                title: title.into(),
+                ..Default::default()
+            },
+            callback_closed: None,
+        }
+    }
+
+    /// start with an indeterminate bar
+    pub fn indeterminate(mut self) -> Self {
+        self.data.value = None;
+        self
+    }
+
+    /// set the initial status line
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.data.status = status.into();
+        self
+    }
+
+    /// whether to close the dialog when the progress reaches 1.0, default is true
+    pub fn close_on_complete(mut self, close_on_complete: bool) -> Self {
+        self.data.close_on_complete = close_on_complete;
+        self
+    }
+
+    /// set the size of the dialog
+    pub fn size(mut self, size: iced::Size) -> Self {
+        self.data.window_settings.size = size;
+        self
+    }
+
+    /// set the cancel button, the dialog result should be `DialogResult::Cancel`
+    pub fn cancel_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.cancel = button;
+        self
+    }
+
+    /// hide the cancel button
+    pub fn hide_cancel_button(mut self) -> Self {
+        self.data.cancel.visible = false;
+        self
+    }
+
+    /// set the callback function when the dialog is closed
+    /// * `DialogResult::Cancel` - the user cancelled, you should cancel the work
+    pub fn on_closed(mut self, callback: WindowCloseCallback) -> Self {
+        self.callback_closed = Some(callback);
+        self
+    }
+
+    /// show the progress dialog window
+    /// * `id` - current window id, this will be a parent id for the new window
+    ///   and the id to send [ProgressMessage]
+    pub fn show(&self, id: iced::window::Id) -> Message {
+        let data = ProgressDialogData {
+            parent: Some(id),
+            ..self.data.clone()
+        };
+        if let Some(callback) = &self.callback_closed {
+            Message::show_dialog(id, data, *callback)
+        } else {
+            Message::show_dialog(id, data, |_| Message::None)
+        }
+    }
+}
+
+/// the indeterminate bar, it animates itself by requesting the redraws
+/// * it stops with the dialog or when the progress is determinate, no message is sent
+struct IndeterminateBar;
+
+/// the time of the first frame, the bar position is computed from it
+#[derive(Debug, Default)]
+struct IndeterminateBarState {
+    started: Option<Instant>,
+    now: Option<Instant>,
+}
+
+impl<Message, Renderer> Widget<Message, Theme, Renderer> for IndeterminateBar
+where
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<IndeterminateBarState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(IndeterminateBarState::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fixed(12.0))
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::atomic(limits, Length::Fill, Length::Fixed(12.0))
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        _layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        if let Event::Window(window::Event::RedrawRequested(now)) = event {
+            let state = tree.state.downcast_mut::<IndeterminateBarState>();
+            state.started.get_or_insert(now);
+            state.now = Some(now);
+            shell.request_redraw(RedrawRequest::At(now + TICK));
+        }
+        event::Status::Ignored
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<IndeterminateBarState>();
+        let bounds = layout.bounds();
+        let palette = theme.extended_palette();
+        renderer.fill_quad(
+            Quad {
+                bounds,
+                ..Default::default()
+            },
+            palette.background.strong.color,
+        );
+        // * the segment enters from the left and leaves from the right
+        let elapsed = match (state.started, state.now) {
+            (Some(started), Some(now)) => now.saturating_duration_since(started),
+            _ => Duration::ZERO,
+        };
+        let phase = (elapsed.as_secs_f32() % PERIOD.as_secs_f32()) / PERIOD.as_secs_f32();
+        let width = bounds.width * SEGMENT;
+        let left = bounds.x - width + (bounds.width + width) * phase;
+        let segment = Rectangle {
+            x: left.max(bounds.x),
+            width: (left + width).min(bounds.x + bounds.width) - left.max(bounds.x),
+            ..bounds
+        };
+        if segment.width > 0.0 {
+            renderer.fill_quad(
+                Quad {
+                    bounds: segment,
+                    ..Default::default()
+                },
+                palette.primary.base.color,
+            );
+        }
+    }
+}
+
+impl<'a, Message, Renderer> From<IndeterminateBar> for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Renderer: 'a + renderer::Renderer,
+{
+    fn from(bar: IndeterminateBar) -> Self {
+        Self::new(bar)
+    }
+}
@@ -0,0 +1,525 @@
+// ! single instance of the app
+// ! the second launch forwards its arguments to the running instance over a local socket and exits
+// ! the local socket is a Unix domain socket on unix, or a named pipe on windows
+// ! the running instance writes a random token into a file only readable by the user,
+// ! and it only accepts the arguments with the token, then acknowledges them
+// ! the socket and the token file are kept in a directory of the user, not the shared temp directory
+
+use iced::futures::channel::mpsc;
+use iced::Subscription;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::program_handle::{receiver_subscription, SharedReceiver};
+
+/// the acknowledgement of the running instance
+const ACK: &[u8; 2] = b"ok";
+/// the max size of the forwarded arguments
+const MAX_PAYLOAD: usize = 1024 * 1024;
+/// how long to wait for the other side
+const TIMEOUT: Duration = Duration::from_secs(2);
+/// the delay between the attempts to forward or bind
+const RETRY: Duration = Duration::from_millis(50);
+
+/// the running instance
+#[derive(Debug, Clone)]
+pub(crate) struct Instance {
+    name: String,
+    receiver: SharedReceiver<Vec<String>>,
+    _guard: Arc<Guard>,
+}
+
+impl Instance {
+    /// forward the arguments to the running instance, or become the running instance
+    /// # Arguments
+    /// * `name` - the unique name of the app
+    /// # Return
+    /// * `Ok(None)` - the arguments are forwarded, this process should exit
+    /// * `Ok(Some(instance))` - this process is the running instance
+    pub(crate) fn acquire(name: &str) -> std::io::Result<Option<Self>> {
+        // * the arguments may be not valid unicode
+        let args: Vec<String> = std::env::args_os()
+            .skip(1)
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        // ! the running instance may be starting, it has bound the socket but not written the token,
+        // ! so both are retried until the timeout instead of starting another instance
+        let deadline = Instant::now() + TIMEOUT;
+        let listener = loop {
+            // * a stale socket or an unrelated peer doesn't acknowledge the token
+            if let Ok(token) = std::fs::read_to_string(token_path(name)?) {
+                if let Ok(mut stream) = local::connect(name) {
+                    if forward(&mut stream, token.trim(), &args).is_ok() {
+                        return Ok(None);
+                    }
+                }
+            }
+            match local::bind(name) {
+                Ok(listener) => break listener,
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::AddrInUse && Instant::now() < deadline =>
+                {
+                    std::thread::sleep(RETRY);
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        let guard = Arc::new(Guard {
+            name: name.to_string(),
+        });
+        let token = new_token();
+        write_token(name, &token)?;
+        let (sender, receiver) = mpsc::unbounded();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+                let Ok(args) = receive(&mut stream, &token) else {
+                    continue;
+                };
+                if sender.unbounded_send(args).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Some(Self {
+            name: name.to_string(),
+            receiver: Arc::new(Mutex::new(Some(receiver))),
+            _guard: guard,
+        }))
+    }
+
+    /// the arguments forwarded by the later launches
+    pub(crate) fn subscription(&self) -> Subscription<Vec<String>> {
+        receiver_subscription(
+            ("single-instance", self.name.clone()),
+            self.receiver.clone(),
+        )
+    }
+
+    /// remove the socket and the token file, it is called when the app exits
+    pub(crate) fn release(&self) {
+        release(&self.name);
+    }
+}
+
+/// remove the files when the last handle of the running instance is dropped
+#[derive(Debug)]
+struct Guard {
+    name: String,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        release(&self.name);
+    }
+}
+
+fn release(name: &str) {
+    if let Ok(path) = token_path(name) {
+        let _ = std::fs::remove_file(path);
+    }
+    local::remove(name);
+}
+
+/// the file saves the token of the running instance
+fn token_path(name: &str) -> std::io::Result<PathBuf> {
+    Ok(user_dir()?.join(format!("{}.token", name)))
+}
+
+/// the directory only accessible by the user
+/// * `XDG_RUNTIME_DIR` if it is set, or a private directory in the temp directory
+#[cfg(unix)]
+fn user_dir() -> std::io::Result<PathBuf> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+    extern "C" {
+        fn getuid() -> u32;
+    }
+
+    if let Some(dir) = std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    let uid = unsafe { getuid() };
+    let dir = std::env::temp_dir().join(format!("iced_kim-{}", uid));
+    match std::fs::DirBuilder::new().mode(0o700).create(&dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+    // ! the directory may be created by another user before us
+    let metadata = std::fs::symlink_metadata(&dir)?;
+    if !metadata.is_dir() || metadata.uid() != uid || metadata.mode() & 0o077 != 0 {
+        return Err(std::io::ErrorKind::PermissionDenied.into());
+    }
+    Ok(dir)
+}
+
+/// the temp directory is in the profile of the user on windows
+#[cfg(not(unix))]
+fn user_dir() -> std::io::Result<PathBuf> {
+    Ok(std::env::temp_dir())
+}
+
+/// a random token, it is not guessable by other processes
+fn new_token() -> String {
+    use std::hash::{BuildHasher, Hasher};
+    let state = std::collections::hash_map::RandomState::new();
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let part = |i: u8| {
+        let mut hasher = state.build_hasher();
+        hasher.write_u8(i);
+        hasher.write_u32(std::process::id());
+        hasher.write_u128(nanos);
+        hasher.finish()
+    };
+    format!("{:016x}{:016x}", part(0), part(1))
+}
+
+/// the token file is only readable by the user
+fn write_token(name: &str, token: &str) -> std::io::Result<()> {
+    let path = token_path(name)?;
+    let _ = std::fs::remove_file(&path);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(token.as_bytes())
+}
+
+/// the later launch: send the token and the arguments, then wait for the acknowledgement
+fn forward(stream: &mut (impl Read + Write), token: &str, args: &[String]) -> std::io::Result<()> {
+    let payload = args.join("\0");
+    stream.write_all(token.as_bytes())?;
+    stream.write_all(b"\n")?;
+    stream.write_all(&(payload.len() as u32).to_le_bytes())?;
+    stream.write_all(payload.as_bytes())?;
+    stream.flush()?;
+    let mut ack = [0u8; 2];
+    stream.read_exact(&mut ack)?;
+    match &ack == ACK {
+        true => Ok(()),
+        false => Err(std::io::ErrorKind::InvalidData.into()),
+    }
+}
+
+/// the running instance: check the token, read the arguments and acknowledge them
+fn receive(stream: &mut (impl Read + Write), token: &str) -> std::io::Result<Vec<String>> {
+    let mut header = vec![0u8; token.len() + 1];
+    stream.read_exact(&mut header)?;
+    if header[..token.len()] != *token.as_bytes() || header[token.len()] != b'\n' {
+        return Err(std::io::ErrorKind::PermissionDenied.into());
+    }
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_PAYLOAD {
+        return Err(std::io::ErrorKind::InvalidData.into());
+    }
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload)?;
+    let content = String::from_utf8(payload)
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidData))?;
+    stream.write_all(ACK)?;
+    Ok(match content.is_empty() {
+        true => vec![],
+        false => content.split('\0').map(String::from).collect(),
+    })
+}
+
+#[cfg(unix)]
+mod local {
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::PathBuf;
+
+    use super::{user_dir, TIMEOUT};
+
+    fn path(name: &str) -> std::io::Result<PathBuf> {
+        Ok(user_dir()?.join(format!("{}.sock", name)))
+    }
+
+    pub(super) fn connect(name: &str) -> std::io::Result<UnixStream> {
+        let stream = UnixStream::connect(path(name)?)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        Ok(stream)
+    }
+
+    /// the running instance side of the socket
+    pub(super) struct Listener(UnixListener);
+
+    impl Listener {
+        /// the accepted connections, a slow peer can't block the running instance
+        pub(super) fn incoming(&self) -> impl Iterator<Item = std::io::Result<UnixStream>> + '_ {
+            self.0.incoming().map(|stream| {
+                let stream = stream?;
+                stream.set_read_timeout(Some(TIMEOUT))?;
+                stream.set_write_timeout(Some(TIMEOUT))?;
+                Ok(stream)
+            })
+        }
+    }
+
+    /// the socket file is left by the crashed instance when nobody is listening
+    /// * it is only removed when the connection is refused, a busy instance may not accept at once
+    /// * `AddrInUse` - another instance is listening
+    pub(super) fn bind(name: &str) -> std::io::Result<Listener> {
+        let path = path(name)?;
+        match UnixStream::connect(&path) {
+            Ok(_) => return Err(std::io::ErrorKind::AddrInUse.into()),
+            Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused => {
+                let _ = std::fs::remove_file(&path);
+            }
+            Err(_) => {}
+        }
+        let listener = UnixListener::bind(&path)?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+        Ok(Listener(listener))
+    }
+
+    pub(super) fn remove(name: &str) {
+        if let Ok(path) = path(name) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(windows)]
+mod local {
+    use std::ffi::c_void;
+    use std::fs::File;
+    use std::os::windows::ffi::OsStrExt;
+    use std::os::windows::io::{AsRawHandle, FromRawHandle};
+
+    use super::TIMEOUT;
+
+    const PIPE_ACCESS_DUPLEX: u32 = 0x0000_0003;
+    const FILE_FLAG_FIRST_PIPE_INSTANCE: u32 = 0x0008_0000;
+    const PIPE_REJECT_REMOTE_CLIENTS: u32 = 0x0000_0008;
+    const PIPE_UNLIMITED_INSTANCES: u32 = 255;
+    const ERROR_ACCESS_DENIED: i32 = 5;
+    const ERROR_PIPE_BUSY: i32 = 231;
+    const ERROR_PIPE_CONNECTED: i32 = 535;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn CreateNamedPipeW(
+            name: *const u16,
+            open_mode: u32,
+            pipe_mode: u32,
+            max_instances: u32,
+            out_buffer_size: u32,
+            in_buffer_size: u32,
+            default_timeout: u32,
+            security_attributes: *mut c_void,
+        ) -> *mut c_void;
+        fn ConnectNamedPipe(pipe: *mut c_void, overlapped: *mut c_void) -> i32;
+        fn WaitNamedPipeW(name: *const u16, timeout: u32) -> i32;
+    }
+
+    /// the pipe names are shared by all the users, so the user name is a part of it
+    fn path(name: &str) -> String {
+        let user = std::env::var("USERNAME").unwrap_or_default();
+        format!(r"\\.\pipe\{}-{}", name, user)
+    }
+
+    fn wide(path: &str) -> Vec<u16> {
+        std::ffi::OsStr::new(path)
+            .encode_wide()
+            .chain(Some(0))
+            .collect()
+    }
+
+    /// connect to the pipe, and wait for a free pipe instance when all are busy
+    pub(super) fn connect(name: &str) -> std::io::Result<File> {
+        let path = path(name);
+        loop {
+            match std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(&path)
+            {
+                Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) => {
+                    let waited =
+                        unsafe { WaitNamedPipeW(wide(&path).as_ptr(), TIMEOUT.as_millis() as u32) };
+                    if waited == 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// create a pipe instance, only the first instance is created by the running instance
+    fn create(name: &[u16], first: bool) -> std::io::Result<File> {
+        let flag = match first {
+            true => FILE_FLAG_FIRST_PIPE_INSTANCE,
+            false => 0,
+        };
+        let handle = unsafe {
+            CreateNamedPipeW(
+                name.as_ptr(),
+                PIPE_ACCESS_DUPLEX | flag,
+                PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                4096,
+                4096,
+                0,
+                std::ptr::null_mut(),
+            )
+        };
+        // * INVALID_HANDLE_VALUE
+        if handle as isize == -1 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(unsafe { File::from_raw_handle(handle) })
+    }
+
+    /// the running instance side of the pipe
+    pub(super) struct Listener {
+        name: Vec<u16>,
+        next: Option<File>,
+    }
+
+    impl Listener {
+        /// wait for the later launches one by one
+        /// * the next pipe instance is created before the connected one is handled,
+        ///   so a concurrent launch waits for it instead of failing with `ERROR_PIPE_BUSY`
+        pub(super) fn incoming(mut self) -> impl Iterator<Item = std::io::Result<File>> {
+            std::iter::from_fn(move || {
+                let pipe = match self.next.take() {
+                    Some(pipe) => pipe,
+                    None => create(&self.name, false).ok()?,
+                };
+                let connected =
+                    unsafe { ConnectNamedPipe(pipe.as_raw_handle(), std::ptr::null_mut()) };
+                if connected == 0 {
+                    let error = std::io::Error::last_os_error();
+                    if error.raw_os_error() != Some(ERROR_PIPE_CONNECTED) {
+                        // * the broken pipe instance is replaced by a new one
+                        self.next = create(&self.name, false).ok();
+                        return Some(Err(error));
+                    }
+                }
+                self.next = create(&self.name, false).ok();
+                Some(Ok(pipe))
+            })
+        }
+    }
+
+    /// the pipe is owned by the running instance
+    /// * `AddrInUse` - another instance owns the pipe
+    pub(super) fn bind(name: &str) -> std::io::Result<Listener> {
+        let name = wide(&path(name));
+        let first = create(&name, true).map_err(|e| match e.raw_os_error() {
+            Some(ERROR_ACCESS_DENIED) => std::io::ErrorKind::AddrInUse.into(),
+            _ => e,
+        })?;
+        Ok(Listener {
+            name,
+            next: Some(first),
+        })
+    }
+
+    /// the pipe is removed by the system with the last handle
+    pub(super) fn remove(_name: &str) {}
+}
+
+#[cfg(not(any(unix, windows)))]
+mod local {
+    use std::fs::File;
+
+    pub(super) fn connect(_name: &str) -> std::io::Result<File> {
+        Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    pub(super) struct Listener;
+
+    impl Listener {
+        pub(super) fn incoming(&self) -> std::iter::Empty<std::io::Result<File>> {
+            std::iter::empty()
+        }
+    }
+
+    pub(super) fn bind(_name: &str) -> std::io::Result<Listener> {
+        Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    pub(super) fn remove(_name: &str) {}
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixStream;
+
+    #[test]
+    fn forward_with_token() {
+        let (mut client, mut server) = UnixStream::pair().unwrap();
+        let handle = std::thread::spawn(move || receive(&mut server, "secret"));
+        let args = vec!["a b".to_string(), "c".to_string()];
+        forward(&mut client, "secret", &args).unwrap();
+        assert_eq!(handle.join().unwrap().unwrap(), args);
+    }
+
+    #[test]
+    fn reject_wrong_token() {
+        let (mut client, mut server) = UnixStream::pair().unwrap();
+        let handle = std::thread::spawn(move || receive(&mut server, "secret"));
+        assert!(forward(&mut client, "public", &[]).is_err());
+        assert!(handle.join().unwrap().is_err());
+    }
+
+    fn unique_name(test: &str) -> String {
+        format!("iced_kim_test_{}_{}", test, std::process::id())
+    }
+
+    #[test]
+    fn user_dir_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = user_dir().unwrap();
+        if std::env::var_os("XDG_RUNTIME_DIR").is_none() {
+            let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+            assert_eq!(mode & 0o077, 0);
+        }
+        assert_ne!(dir, std::env::temp_dir());
+    }
+
+    #[test]
+    fn bind_keeps_live_socket() {
+        let name = unique_name("live");
+        let _listener = local::bind(&name).unwrap();
+        let error = local::bind(&name).err().unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::AddrInUse);
+        assert!(local::connect(&name).is_ok());
+        local::remove(&name);
+    }
+
+    #[test]
+    fn bind_replaces_stale_socket() {
+        let name = unique_name("stale");
+        drop(local::bind(&name).unwrap());
+        assert!(local::bind(&name).is_ok());
+        local::remove(&name);
+    }
+
+    #[test]
+    fn acquire_forwards_to_running_instance() {
+        let name = unique_name("acquire");
+        let instance = Instance::acquire(&name).unwrap().unwrap();
+        let mut receiver = instance.receiver.lock().unwrap().take().unwrap();
+        assert!(Instance::acquire(&name).unwrap().is_none());
+        let args = iced::futures::executor::block_on(iced::futures::StreamExt::next(&mut receiver));
+        let expected: Vec<String> = std::env::args().skip(1).collect();
+        assert_eq!(args, Some(expected));
+        instance.release();
+    }
+}
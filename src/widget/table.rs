@@ -54,6 +54,8 @@ where
     heads: &'a Vec<TableHead>,
     /// the font of table
     font: Font,
+    /// the text size of table
+    text_size: Pixels,
     /// the source data of table
     source: &'a Vec<T>,
     /// whether to display a row background color in zebra type
@@ -96,7 +98,8 @@ where
             heads,
             head_height: Length::Fixed(40.0),
             row_height: Length::Fixed(30.0),
-            font: Font::with_name("微软雅黑"),
+            font: Font::DEFAULT,
+            text_size: Pixels(16.0),
             source,
             show_stripe: true,
             on_row_selected: None,
@@ -134,11 +137,19 @@ where
         self
     }
 
+    /// Sets the font of the [`Table`], the default font of the program by default.
+    /// * it can be changed at runtime, e.g. by the font chosen in the font dialog
     pub fn font(mut self, font: Font) -> Self {
         self.font = font;
         self
     }
 
+    /// Sets the text size of the [`Table`].
+    pub fn text_size(mut self, size: impl Into<Pixels>) -> Self {
+        self.text_size = size.into();
+        self
+    }
+
     pub fn show_stripe(mut self, show_stripe: bool) -> Self {
         self.show_stripe = show_stripe;
         self
@@ -799,7 +810,13 @@ where
                 );
                 // * draw head cell text
                 renderer.fill_text(
-                    self.get_fill_text(&head.text, 16, rect.size(), head.align_x, head.align_y),
+                    self.get_fill_text(
+                        &head.text,
+                        self.text_size,
+                        rect.size(),
+                        head.align_x,
+                        head.align_y,
+                    ),
                     self.get_text_position(&rect, head.align_x, head.align_y),
                     style.head_color,
                     rect,
@@ -841,7 +858,13 @@ where
                 );
                 // * draw cell text
                 renderer.fill_text(
-                    self.get_fill_text(&head.text, 16, rect.size(), head.align_x, head.align_y),
+                    self.get_fill_text(
+                        &head.text,
+                        self.text_size,
+                        rect.size(),
+                        head.align_x,
+                        head.align_y,
+                    ),
                     self.get_text_position(&rect, head.align_x, head.align_y),
                     style.text_color,
                     rect,
@@ -918,7 +941,13 @@ where
                 );
                 // * draw cell text
                 renderer.fill_text(
-                    self.get_fill_text(value, 16, rect.size(), head.align_x, head.align_y),
+                    self.get_fill_text(
+                        value,
+                        self.text_size,
+                        rect.size(),
+                        head.align_x,
+                        head.align_y,
+                    ),
                     self.get_text_position(&rect, head.align_x, head.align_y),
                     style.text_color,
                     rect,
@@ -1034,7 +1063,13 @@ where
                         );
                         // * draw cell text
                         renderer.fill_text(
-                            self.get_fill_text(value, 16, rect.size(), head.align_x, head.align_y),
+                            self.get_fill_text(
+                                value,
+                                self.text_size,
+                                rect.size(),
+                                head.align_x,
+                                head.align_y,
+                            ),
                             self.get_text_position(&rect, head.align_x, head.align_y),
                             style.text_color,
                             rect,
@@ -1072,7 +1107,13 @@ where
                         );
                         // * draw cell text
                         renderer.fill_text(
-                            self.get_fill_text(value, 16, rect.size(), head.align_x, head.align_y),
+                            self.get_fill_text(
+                                value,
+                                self.text_size,
+                                rect.size(),
+                                head.align_x,
+                                head.align_y,
+                            ),
                             self.get_text_position(&rect, head.align_x, head.align_y),
                             style.text_color,
                             rect,
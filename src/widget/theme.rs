@@ -0,0 +1,227 @@
+// ! custom themes from palette files
+// ! the palette file is TOML or JSON by its extension, like this:
+// ! name = "Brand"
+// ! background = "#ffffff"
+// ! text = "#1e1e2e"
+// ! primary = "#d20f39"
+// ! success = "#40a02b"
+// ! danger = "#e64553"
+
+use iced::futures::{stream, StreamExt};
+use iced::theme::Palette;
+use iced::{Color, Subscription, Theme};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::timer::ticks;
+use crate::core::Message;
+
+/// how often the palette file is checked by [watch_theme]
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// the palette file content, every color is a hex string like `#rrggbb` or `#rrggbbaa`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThemePalette {
+    /// the theme name
+    #[serde(default = "ThemePalette::default_name")]
+    pub name: String,
+    pub background: String,
+    pub text: String,
+    pub primary: String,
+    pub success: String,
+    pub danger: String,
+}
+
+impl ThemePalette {
+    fn default_name() -> String {
+        "Custom".into()
+    }
+
+    /// parse the palette from the TOML content
+    pub fn from_toml(content: &str) -> Result<Self, ThemeError> {
+        toml::from_str(content).map_err(|e| ThemeError::Parse(e.to_string()))
+    }
+
+    /// parse the palette from the JSON content
+    pub fn from_json(content: &str) -> Result<Self, ThemeError> {
+        serde_json::from_str(content).map_err(|e| ThemeError::Parse(e.to_string()))
+    }
+
+    /// read the palette file, the format is decided by the extension `toml` or `json`
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| ThemeError::Io(e.to_string()))?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Self::from_toml(&content),
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::from_json(&content),
+            _ => Err(ThemeError::Format(path.display().to_string())),
+        }
+    }
+
+    /// build the `Theme::custom` by the palette
+    pub fn to_theme(&self) -> Result<Theme, ThemeError> {
+        let palette = Palette {
+            background: parse_color("background", &self.background)?,
+            text: parse_color("text", &self.text)?,
+            primary: parse_color("primary", &self.primary)?,
+            success: parse_color("success", &self.success)?,
+            danger: parse_color("danger", &self.danger)?,
+        };
+        Ok(Theme::custom(self.name.clone(), palette))
+    }
+}
+
+/// the error of loading a palette file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeError {
+    /// the file can't be read
+    Io(String),
+    /// the content is not a valid palette
+    Parse(String),
+    /// the color of the field is not a hex color
+    Color { field: String, value: String },
+    /// the file extension is neither `toml` nor `json`
+    Format(String),
+}
+
+impl std::fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeError::Io(e) => write!(f, "can't read the palette file: {}", e),
+            ThemeError::Parse(e) => write!(f, "invalid palette: {}", e),
+            ThemeError::Color { field, value } => {
+                write!(f, "invalid color of `{}`: {}", field, value)
+            }
+            ThemeError::Format(path) => write!(f, "unknown palette format: {}", path),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+fn parse_color(field: &str, value: &str) -> Result<Color, ThemeError> {
+    Color::parse(value.trim()).ok_or_else(|| ThemeError::Color {
+        field: field.into(),
+        value: value.into(),
+    })
+}
+
+/// load a custom theme from the palette file
+/// * `path` - the TOML or JSON palette file
+pub fn load_theme(path: impl AsRef<Path>) -> Result<Theme, ThemeError> {
+    ThemePalette::from_file(path)?.to_theme()
+}
+
+/// watch the palette file and send `Message::Theme` to all windows when it changes
+/// * the invalid file is reported and skipped, the current theme is kept
+/// * `path` - the TOML or JSON palette file
+pub fn watch_theme(path: impl Into<PathBuf>) -> Subscription<Message> {
+    let path = path.into();
+    let modified = modified_time(&path);
+    Subscription::run_with_id(
+        path.clone(),
+        stream::unfold(
+            (ticks(WATCH_INTERVAL).boxed(), path, modified),
+            |(mut ticks, path, mut modified)| async move {
+                while ticks.next().await.is_some() {
+                    let current = modified_time(&path);
+                    if current == modified {
+                        continue;
+                    }
+                    modified = current;
+                    match load_theme(&path) {
+                        Ok(theme) => return Some((theme, (ticks, path, modified))),
+                        Err(e) => eprintln!("iced_kim: {} ({})", e, path.display()),
+                    }
+                }
+                None
+            },
+        ),
+    )
+    .map(Message::Theme)
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_toml_palette() {
+        let palette = ThemePalette::from_toml(
+            r##"
+            name = "Dark"
+            background = "#202020"
+            text = "#ffffff"
+            primary = "#3366ff"
+            success = "#12a150"
+            danger = "#e03030"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(palette.name, "Dark");
+        assert_eq!(palette.primary, "#3366ff");
+
+        let theme = palette.to_theme().unwrap();
+        assert_eq!(theme.to_string(), "Dark");
+        assert_eq!(theme.palette().text, Color::WHITE);
+    }
+
+    #[test]
+    fn parse_json_palette() {
+        let palette = ThemePalette::from_json(
+            r##"{
+                "background": "#ffffff",
+                "text": "#000000",
+                "primary": "#3366ff",
+                "success": "#12a150",
+                "danger": "#e03030"
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(palette.name, "Custom");
+        assert_eq!(palette.to_theme().unwrap().palette().text, Color::BLACK);
+    }
+
+    #[test]
+    fn invalid_palette() {
+        assert!(matches!(
+            ThemePalette::from_toml("background = \"#ffffff\""),
+            Err(ThemeError::Parse(_))
+        ));
+        assert!(matches!(
+            ThemePalette::from_json("{"),
+            Err(ThemeError::Parse(_))
+        ));
+
+        let palette = ThemePalette {
+            name: "Broken".into(),
+            background: "#ffffff".into(),
+            text: "black".into(),
+            primary: "#3366ff".into(),
+            success: "#12a150".into(),
+            danger: "#e03030".into(),
+        };
+        assert_eq!(
+            palette.to_theme(),
+            Err(ThemeError::Color {
+                field: "text".into(),
+                value: "black".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_palette_format() {
+        let path =
+            std::env::temp_dir().join(format!("iced_kim_palette_{}.yaml", std::process::id()));
+        std::fs::write(&path, "name: Dark").unwrap();
+        let result = ThemePalette::from_file(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(result, Err(ThemeError::Format(path.display().to_string())));
+    }
+}
@@ -0,0 +1,31 @@
+// ! a timer without an async runtime, iced only has `time::every` with the tokio or smol feature
+
+use iced::futures::channel::mpsc;
+use iced::futures::{stream, Stream, StreamExt};
+use iced::Subscription;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+/// a stream ticking with the interval
+/// * one thread sleeps for all the ticks, it stops when the stream is dropped
+pub(crate) fn ticks(interval: Duration) -> impl Stream<Item = Instant> {
+    stream::once(async move {
+        let (sender, receiver) = mpsc::unbounded();
+        std::thread::spawn(move || loop {
+            std::thread::sleep(interval);
+            if sender.unbounded_send(Instant::now()).is_err() {
+                break;
+            }
+        });
+        receiver
+    })
+    .flatten()
+}
+
+/// a subscription ticking with the interval, see [ticks]
+pub(crate) fn every<I>(id: I, interval: Duration) -> Subscription<Instant>
+where
+    I: Hash + 'static,
+{
+    Subscription::run_with_id(id, ticks(interval))
+}
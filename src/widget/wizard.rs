@@ -0,0 +1,306 @@
+// ! a multi-step wizard dialog
+// ! the wizard is closed with `DialogResult::Ok` by finish or `DialogResult::Cancel` by cancel
+
+use iced::widget::{button, column, container, horizontal_rule, row, text};
+use iced::window::Level;
+use iced::{window, Alignment, Element, Length};
+
+use super::{message_box_button, MessageBoxButton};
+use crate::core::{
+    DialogResult, EventMessage, IWindow, IWindowMessage, Message, Window, WindowCloseCallback,
+    WindowPlacement,
+};
+
+/// create a new wizard dialog with a new window
+/// * `title` - the title of the wizard
+pub fn wizard(title: impl Into<String>) -> Wizard {
+    Wizard::new(title)
+}
+
+/// a trait for a step page of the [Wizard]
+pub trait IWizardStep: dyn_clone::DynClone + downcast_rs::Downcast + Send {
+    /// the step title
+    fn title(&self) -> String;
+
+    /// get the view of the step
+    /// # Arguments
+    /// * `id` - the wizard window id
+    fn view(&self, id: window::Id) -> Element<'_, Message>;
+
+    /// this is where the message is handled when the step is shown
+    /// # Arguments
+    /// * `id` - the wizard window id
+    /// * `message` - the global message, you need check the message type
+    fn update(
+        &mut self,
+        id: &window::Id,
+        message: &std::boxed::Box<dyn IWindowMessage>,
+    ) -> Message {
+        let _ = id;
+        let _ = message;
+        Message::None
+    }
+
+    /// validate the step, the next and finish button are disabled when it returns an error
+    /// # Return
+    /// * `Err(reason)` - the reason shown to the user
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+dyn_clone::clone_trait_object!(IWizardStep);
+downcast_rs::impl_downcast!(IWizardStep);
+impl std::fmt::Debug for dyn IWizardStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.title())
+    }
+}
+
+/// the wizard message, the window id is the wizard window
+#[derive(Debug, Clone)]
+pub enum WizardMessage {
+    Back(window::Id),
+    Next(window::Id),
+}
+
+impl IWindowMessage for WizardMessage {}
+
+/// ! the wizard window data
+/// * get it in the close callback by `get_dialog_data::<WizardData>()`
+#[derive(Debug, Clone)]
+pub struct WizardData {
+    pub title: String,
+    pub steps: Vec<Box<dyn IWizardStep>>,
+    pub current: usize,
+    pub back: MessageBoxButton,
+    pub next: MessageBoxButton,
+    pub finish: MessageBoxButton,
+    pub cancel: MessageBoxButton,
+    pub window_settings: iced::window::Settings,
+}
+
+impl Default for WizardData {
+    fn default() -> Self {
+        Self {
+            title: "向导".into(),
+            steps: Vec::new(),
+            current: 0,
+            back: message_box_button("上一步"),
+            next: message_box_button("下一步"),
+            finish: message_box_button("完成").dialog_result(DialogResult::Ok),
+            cancel: message_box_button("取消").dialog_result(DialogResult::Cancel),
+            window_settings: iced::window::Settings {
+                min_size: Some(iced::Size::new(500.0, 360.0)),
+                size: iced::Size::new(600.0, 420.0),
+                position: iced::window::Position::Centered,
+                level: Level::AlwaysOnTop,
+                resizable: true,
+                icon: None,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl WizardData {
+    /// find the first step by its type
+    pub fn step<T>(&self) -> Option<&T>
+    where
+        T: IWizardStep,
+    {
+        self.steps.iter().find_map(|step| step.downcast_ref::<T>())
+    }
+
+    /// whether the current step is the last step
+    pub fn is_last(&self) -> bool {
+        self.current + 1 >= self.steps.len()
+    }
+}
+
+impl IWindow for WizardData {
+    fn new_window(&self) -> Window {
+        Window::builder(self.to_owned())
+            .title(self.title.clone())
+            .settings(self.window_settings.clone())
+            .placement(WindowPlacement::CenteredOnParent)
+            .build()
+    }
+
+    fn update(
+        &mut self,
+        id: &window::Id,
+        message: &std::boxed::Box<dyn IWindowMessage>,
+    ) -> Message {
+        match message.downcast_ref::<WizardMessage>() {
+            Some(WizardMessage::Back(wizard_id)) if wizard_id == id => {
+                self.current = self.current.saturating_sub(1);
+                Message::None
+            }
+            Some(WizardMessage::Next(wizard_id)) if wizard_id == id => {
+                let valid = self
+                    .steps
+                    .get(self.current)
+                    .is_some_and(|step| step.validate().is_ok());
+                if valid && !self.is_last() {
+                    self.current += 1;
+                }
+                Message::None
+            }
+            Some(_) => Message::None,
+            None => match self.steps.get_mut(self.current) {
+                Some(step) => step.update(id, message),
+                None => Message::None,
+            },
+        }
+    }
+
+    fn on_close_request(&self, id: window::Id) -> Message {
+        EventMessage::Close(id, self.cancel.message.clone()).into()
+    }
+
+    fn view(&self, _window: &Window, id: window::Id) -> Element<'_, Message> {
+        let Some(step) = self.steps.get(self.current) else {
+            return column![].into();
+        };
+        let validation = step.validate();
+
+        let header = row![
+            text(step.title()).size(18).width(Length::Fill),
+            text(format!("{} / {}", self.current + 1, self.steps.len())),
+        ]
+        .align_y(Alignment::Center);
+
+        let content = container(step.view(id))
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let error = validation.clone().err().map(|reason| {
+            text(reason).style(|theme: &iced::Theme| text::Style {
+                color: Some(theme.extended_palette().danger.base.color),
+            })
+        });
+
+        let button_back = self
+            .back
+            .to_button()
+            .on_press_maybe((self.current > 0).then(|| WizardMessage::Back(id).into()))
+            .style(button::secondary);
+
+        let button_next = if self.is_last() {
+            self.finish.to_button().on_press_maybe(
+                validation
+                    .is_ok()
+                    .then(|| EventMessage::Close(id, self.finish.message.clone()).into()),
+            )
+        } else {
+            self.next
+                .to_button()
+                .on_press_maybe(validation.is_ok().then(|| WizardMessage::Next(id).into()))
+        }
+        .style(button::primary);
+
+        let button_cancel = self
+            .cancel
+            .to_button()
+            .on_press(EventMessage::Close(id, self.cancel.message.clone()).into())
+            .style(button::secondary);
+
+        let button_group = row![]
+            .push_maybe(self.cancel.visible.then_some(button_cancel))
+            .push_maybe(self.back.visible.then_some(button_back))
+            .push(button_next)
+            .align_y(Alignment::Center)
+            .spacing(20);
+
+        let button_layout = column![button_group]
+            .align_x(Alignment::End)
+            .width(Length::Fill);
+
+        column![header, horizontal_rule(1), content]
+            .push_maybe(error)
+            .push(button_layout)
+            .spacing(10)
+            .padding(10)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
+
+/// ! the wizard builder
+pub struct Wizard {
+    data: WizardData,
+    callback_closed: Option<WindowCloseCallback>,
+}
+
+#[allow(unused)]
+impl Wizard {
+    /// create a new wizard with the title
+    /// * `title` - the title of the wizard window
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            data: WizardData {
+                title: title.into(),
+                ..Default::default()
+            },
+            callback_closed: None,
+        }
+    }
+
+    /// add a step page
+    /// * `step` - the step page
+    pub fn step<T>(mut self, step: T) -> Self
+    where
+        T: IWizardStep,
+    {
+        self.data.steps.push(Box::new(step));
+        self
+    }
+
+    /// set the size of the wizard
+    pub fn size(mut self, size: iced::Size) -> Self {
+        self.data.window_settings.size = size;
+        self
+    }
+
+    /// set the back button
+    pub fn back_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.back = button;
+        self
+    }
+
+    /// set the next button
+    pub fn next_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.next = button;
+        self
+    }
+
+    /// set the finish button, the dialog result should be `DialogResult::Ok`
+    pub fn finish_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.finish = button;
+        self
+    }
+
+    /// set the cancel button, the dialog result should be `DialogResult::Cancel`
+    pub fn cancel_button(mut self, button: MessageBoxButton) -> Self {
+        self.data.cancel = button;
+        self
+    }
+
+    /// set the callback function when the wizard is closed
+    /// * get the collected data by `get_dialog_data::<WizardData>()`
+    pub fn on_closed(mut self, callback: WindowCloseCallback) -> Self {
+        self.callback_closed = Some(callback);
+        self
+    }
+
+    /// show the wizard window
+    /// * `id` - current window id, this will be a parent id for the new window
+    pub fn show(&self, id: iced::window::Id) -> Message {
+        if let Some(callback) = &self.callback_closed {
+            Message::show_dialog(id, self.data.clone(), *callback)
+        } else {
+            Message::show_dialog(id, self.data.clone(), |_| Message::None)
+        }
+    }
+}